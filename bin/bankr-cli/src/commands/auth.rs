@@ -3,7 +3,12 @@ use std::{io::Write, path::Path};
 use bankr_agent_api::BankrAgentClient;
 use eyre::{Result, eyre};
 
-use crate::{config, display};
+use super::profile::validate_name;
+use crate::{
+    config::{self, BankrConfig, Profile},
+    display,
+    keystore::{self, EncryptedKey},
+};
 
 /// Prompt the user for an API key on stdin (interactive).
 fn read_api_key_interactive() -> Result<String> {
@@ -18,12 +23,49 @@ fn read_api_key_interactive() -> Result<String> {
     Ok(trimmed)
 }
 
-/// `bankr-cli login [--api-key KEY]`
+/// The profile called `name`, created if it does not exist yet.
+///
+/// # Errors
+///
+/// Fails if the profile would be created under an invalid name.
+fn profile_entry<'a>(cfg: &'a mut BankrConfig, name: &str) -> Result<&'a mut Profile> {
+    if name == config::DEFAULT_PROFILE {
+        return Ok(&mut cfg.default);
+    }
+    if !cfg.profiles.contains_key(name) {
+        validate_name(name)?;
+    }
+    Ok(cfg.profiles.entry(name.to_owned()).or_default())
+}
+
+/// `bankr-cli login [--api-key KEY] [--llm-key KEY] [--encrypt]`
+///
+/// Stores the keys in the active profile, creating it if needed. With
+/// `--encrypt` the API key is stored as `encryptedApiKey`, sealed with a
+/// passphrase, and any plain `apiKey` is removed; it cannot be combined with
+/// `--llm-key`, which would be stored in plain text.
+/// `--llm-key` alone stores only the LLM Gateway key, without prompting for
+/// or verifying an API key.
 pub(crate) async fn cmd_login(
     api_key_flag: Option<&str>,
+    llm_key: Option<&str>,
+    encrypt: bool,
     base_url: Option<&str>,
     config_path: &Path,
+    profile: Option<&str>,
 ) -> Result<()> {
+    let mut cfg = config::load(config_path)?;
+    let name = cfg.active_profile_name(profile).to_owned();
+    // Reject an invalid new profile name before asking for a key.
+    profile_entry(&mut cfg, &name)?;
+
+    if let (None, Some(llm_key)) = (api_key_flag, llm_key) {
+        profile_entry(&mut cfg, &name)?.llm_key = Some(llm_key.to_owned());
+        config::save(config_path, &cfg)?;
+        display::success(&format!("LLM Gateway key saved to profile {name}"));
+        return Ok(());
+    }
+
     let api_key = match api_key_flag {
         Some(k) => k.to_owned(),
         None => read_api_key_interactive()?,
@@ -37,23 +79,41 @@ pub(crate) async fn cmd_login(
 
     client.get_me().await.map_err(|e| eyre!("login failed: {e}"))?;
 
+    let encrypted = if encrypt {
+        Some(EncryptedKey::seal(&api_key, &keystore::new_passphrase()?)?)
+    } else {
+        None
+    };
+
     // Persist to config.
-    let mut cfg = config::load(config_path);
-    cfg.api_key = Some(api_key);
+    let entry = profile_entry(&mut cfg, &name)?;
+    entry.api_key = encrypted.is_none().then_some(api_key);
+    entry.encrypted_api_key = encrypted;
+    if let Some(llm_key) = llm_key {
+        entry.llm_key = Some(llm_key.to_owned());
+    }
     if let Some(url) = base_url {
-        cfg.api_url = Some(url.to_owned());
+        entry.api_url = Some(url.to_owned());
     }
     config::save(config_path, &cfg)?;
 
-    display::success("Logged in successfully");
+    let stored = if encrypt { ", key encrypted" } else { "" };
+    display::success(&format!("Logged in successfully (profile {name}{stored})"));
     Ok(())
 }
 
-/// `bankr-cli logout`
-pub(crate) fn cmd_logout(config_path: &Path) -> Result<()> {
-    let mut cfg = config::load(config_path);
-    cfg.api_key = None;
+/// `bankr-cli logout` — clear the active profile's API key, plain or
+/// encrypted.
+pub(crate) fn cmd_logout(config_path: &Path, profile: Option<&str>) -> Result<()> {
+    let mut cfg = config::load(config_path)?;
+    let name = cfg.active_profile_name(profile).to_owned();
+    let entry = cfg.profile_mut(&name).ok_or_else(|| config::unknown_profile(&name))?;
+    entry.api_key = None;
+    entry.encrypted_api_key = None;
     config::save(config_path, &cfg)?;
-    display::success(&format!("Logged out. Credentials removed from {}", config_path.display()));
+    display::success(&format!(
+        "Logged out of profile {name}. Credentials removed from {}",
+        config_path.display()
+    ));
     Ok(())
 }
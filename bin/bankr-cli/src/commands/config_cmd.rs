@@ -1,21 +1,123 @@
 use std::path::Path;
 
-use eyre::Result;
+use bankr_agent_api::llm;
+use clap::Subcommand;
+use eyre::{Result, eyre};
 
-use crate::config;
+use crate::{
+    config::{self, ConfigKey},
+    display, print_json,
+};
 
-/// `bankr-cli config` — display current configuration.
-pub(crate) fn cmd_config(config_path: &Path) -> Result<()> {
-    let cfg = config::load(config_path);
+#[derive(Debug, Subcommand)]
+pub(crate) enum ConfigCommands {
+    /// Print one setting of the active profile.
+    Get {
+        /// Setting name, e.g. `apiUrl` (see `config list`).
+        key: ConfigKey,
 
-    let masked_key =
-        cfg.api_key.as_deref().map_or_else(|| "Not set".to_owned(), config::mask_api_key);
+        /// Print secrets in full instead of masked.
+        #[arg(long, default_value_t = false)]
+        reveal: bool,
+    },
+
+    /// Change a setting of the active profile.
+    Set {
+        /// Setting name, e.g. `apiUrl` (see `config list`).
+        key: ConfigKey,
+
+        /// New value.
+        value: String,
+    },
+
+    /// Remove a setting from the active profile.
+    Unset {
+        /// Setting name, e.g. `apiUrl` (see `config list`).
+        key: ConfigKey,
+    },
+
+    /// Print every setting of the active profile.
+    List,
+
+    /// Print the path of the config file.
+    Path,
+}
+
+/// `bankr-cli config` — display the active profile's configuration.
+pub(crate) fn cmd_config(config_path: &Path, profile: Option<&str>) -> Result<()> {
+    let cfg = config::load(config_path)?;
+    let (name, cfg) = cfg.active_profile(profile)?;
+
+    let masked_key = match (&cfg.api_key_command, &cfg.encrypted_api_key, &cfg.api_key) {
+        (Some(command), _, _) => format!("From `{command}`"),
+        (None, Some(_), _) => "Encrypted (passphrase required)".to_owned(),
+        (None, None, Some(key)) => config::mask_api_key(key),
+        (None, None, None) => "Not set".to_owned(),
+    };
 
     let api_url = cfg.api_url.as_deref().unwrap_or("https://api.bankr.bot");
 
+    let masked_llm_key = cfg
+        .llm_key
+        .as_deref()
+        .map_or_else(|| "Not set (uses API key)".to_owned(), config::mask_api_key);
+    let llm_url = cfg.llm_url.as_deref().unwrap_or(llm::DEFAULT_BASE_URL);
+
     println!("Config file:  {}", config_path.display());
+    println!("Profile:      {name}");
     println!("API Key:      {masked_key}");
     println!("API URL:      {api_url}");
+    println!("LLM Key:      {masked_llm_key}");
+    println!("LLM URL:      {llm_url}");
+
+    Ok(())
+}
+
+/// `bankr-cli config <get|set|unset|list|path>`
+pub(crate) fn cmd_config_key(
+    config_path: &Path,
+    command: &ConfigCommands,
+    profile: Option<&str>,
+    raw: bool,
+) -> Result<()> {
+    let mut cfg = config::load(config_path)?;
+    let name = cfg.active_profile_name(profile).to_owned();
 
+    match command {
+        ConfigCommands::Path => println!("{}", config_path.display()),
+        ConfigCommands::Get { key, reveal } => {
+            let (_, active) = cfg.active_profile(profile)?;
+            let value = if *reveal { key.get(active) } else { key.display(active) };
+            let value = value.ok_or_else(|| eyre!("{key} is not set in profile {name}"))?;
+            println!("{value}");
+        }
+        ConfigCommands::List => {
+            let (_, active) = cfg.active_profile(profile)?;
+            if raw {
+                let map: serde_json::Map<_, _> = ConfigKey::ALL
+                    .into_iter()
+                    .filter_map(|key| Some((key.name().to_owned(), key.display(active)?.into())))
+                    .collect();
+                return print_json(&map, raw);
+            }
+            for key in ConfigKey::ALL {
+                let value = key.display(active).unwrap_or_else(|| "(not set)".to_owned());
+                println!("{:<16} {value}", key.name());
+            }
+        }
+        ConfigCommands::Set { key, value } => {
+            let active = cfg.profile_mut(&name).ok_or_else(|| config::unknown_profile(&name))?;
+            key.set(active, value)?;
+            let shown = key.display(active).unwrap_or_default();
+            config::save(config_path, &cfg)?;
+            display::success(&format!("Set {key} = {shown} (profile {name})"));
+        }
+        ConfigCommands::Unset { key } => {
+            let active = cfg.profile_mut(&name).ok_or_else(|| config::unknown_profile(&name))?;
+            key.unset(active);
+            config::save(config_path, &cfg)?;
+            display::success(&format!("Unset {key} (profile {name})"));
+        }
+    }
     Ok(())
 }
@@ -0,0 +1,120 @@
+use std::io::Write;
+
+use bankr_agent_api::llm::{self, ChatCompletionRequest, ChatMessage, LlmClient, Usage};
+use clap::{Args, Subcommand};
+use eyre::{Result, eyre};
+use futures_util::StreamExt;
+
+use crate::{
+    config::{self, Profile},
+    print_json,
+};
+
+#[derive(Debug, Args)]
+pub(crate) struct LlmArgs {
+    /// LLM Gateway key (default: the profile's `llmKey`, then the API key).
+    #[arg(long, env = "BANKR_LLM_KEY", global = true, hide_env_values = true)]
+    llm_key: Option<String>,
+
+    /// LLM Gateway URL override (default: https://llm.bankr.bot).
+    #[arg(long, env = "BANKR_LLM_URL", global = true)]
+    llm_url: Option<String>,
+
+    #[command(subcommand)]
+    command: LlmCommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum LlmCommands {
+    /// Send a chat message and print the reply as it is generated.
+    Chat {
+        /// The message text.
+        prompt: String,
+
+        /// Model ID (see `bankr-cli llm models`).
+        #[arg(long, default_value = "claude-sonnet-4.5")]
+        model: String,
+
+        /// System prompt.
+        #[arg(long)]
+        system: Option<String>,
+
+        /// Maximum number of tokens to generate.
+        #[arg(long)]
+        max_tokens: Option<u32>,
+
+        /// Sampling temperature.
+        #[arg(long)]
+        temperature: Option<f64>,
+
+        /// Wait for the whole reply and print the completion as JSON.
+        #[arg(long, default_value_t = false)]
+        no_stream: bool,
+    },
+
+    /// List the models available to the key.
+    Models,
+}
+
+/// `bankr-cli llm <chat|models>`
+pub(crate) async fn cmd_llm(
+    args: &LlmArgs,
+    api_key: Option<&str>,
+    api_key_command: Option<&str>,
+    cfg: &Profile,
+    raw: bool,
+) -> Result<()> {
+    let llm_key = config::resolve_llm_key(args.llm_key.as_deref(), api_key, api_key_command, cfg)?
+        .ok_or_else(|| {
+        eyre!(
+            "LLM key required. Set via --llm-key, BANKR_LLM_KEY env var, `bankr-cli login --llm-key`, or use an API key with LLM Gateway access."
+        )
+    })?;
+    let llm_url =
+        args.llm_url.as_deref().or(cfg.llm_url.as_deref()).unwrap_or(llm::DEFAULT_BASE_URL);
+    let client = LlmClient::with_base_url(&llm_key, llm_url).map_err(|e| eyre!("{e}"))?;
+
+    match &args.command {
+        LlmCommands::Models => {
+            let models = client.list_models().await.map_err(|e| eyre!("{e}"))?;
+            print_json(&models, raw)
+        }
+        LlmCommands::Chat { prompt, model, system, max_tokens, temperature, no_stream } => {
+            let mut messages: Vec<ChatMessage> = system.iter().map(ChatMessage::system).collect();
+            messages.push(ChatMessage::user(prompt));
+            let mut req = ChatCompletionRequest::new(model, messages);
+            req.max_tokens = *max_tokens;
+            req.temperature = *temperature;
+
+            if *no_stream {
+                let completion = client.chat_completion(&req).await.map_err(|e| eyre!("{e}"))?;
+                return print_json(&completion, raw);
+            }
+            stream_chat(&client, &req).await
+        }
+    }
+}
+
+/// Print deltas to stdout as they arrive, then token usage to stderr.
+async fn stream_chat(client: &LlmClient, req: &ChatCompletionRequest) -> Result<()> {
+    let stream = client.chat_completion_stream(req).await.map_err(|e| eyre!("{e}"))?;
+    let mut stream = std::pin::pin!(stream);
+    let mut stdout = std::io::stdout();
+    let mut usage: Option<Usage> = None;
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| eyre!("{e}"))?;
+        write!(stdout, "{}", chunk.text())?;
+        stdout.flush()?;
+        usage = chunk.usage.or(usage);
+    }
+    writeln!(stdout)?;
+
+    if let Some(usage) = usage {
+        eprintln!(
+            "tokens: {} prompt + {} completion = {}",
+            usage.prompt_tokens, usage.completion_tokens, usage.total_tokens
+        );
+    }
+    Ok(())
+}
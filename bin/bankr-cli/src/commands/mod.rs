@@ -1,8 +1,82 @@
 pub(crate) mod auth;
 pub(crate) mod config_cmd;
 pub(crate) mod job;
+pub(crate) mod llm;
+pub(crate) mod profile;
 pub(crate) mod prompt;
 pub(crate) mod sign;
 pub(crate) mod skills;
 pub(crate) mod submit;
 pub(crate) mod whoami;
+
+use bankr_agent_api::{chain::Chain, primitives::U256, units};
+use eyre::{Result, eyre};
+
+/// Clap value parser for `--chain`: a registry name or alias (`base`, `eth`)
+/// or a numeric chain ID. Unknown IDs are accepted; `submit` warns about
+/// them.
+pub(crate) fn parse_chain(s: &str) -> Result<u64, String> {
+    if let Ok(chain) = s.parse::<Chain>() {
+        return Ok(chain.id());
+    }
+    s.parse().map_err(|_| {
+        format!("unknown chain {s:?}; use a chain ID or one of ethereum, base, polygon, unichain")
+    })
+}
+
+/// The `--chain` argument, falling back to the profile's `defaultChain`.
+pub(crate) fn resolve_chain(chain_id: Option<u64>, default_chain: Option<u64>) -> Result<u64> {
+    chain_id.or(default_chain).ok_or_else(|| {
+        eyre!("--chain is required (or set a default: `bankr-cli config set defaultChain base`)")
+    })
+}
+
+/// Parse a wei amount for `chain_id`: `0.1ether`, `2 gwei`, the chain's
+/// native symbol (`0.1eth` on Base, `1pol` on Polygon), or a bare decimal or
+/// `0x` hex integer in wei. Yields the decimal wei string the API expects.
+///
+/// Another chain's native symbol is rejected, as are all symbols on chains
+/// outside the registry.
+pub(crate) fn parse_wei(s: &str, chain_id: u64) -> Result<String, String> {
+    let trimmed = s.trim();
+    if trimmed.starts_with("0x") || trimmed.starts_with("0X") {
+        return trimmed.parse::<U256>().map(|wei| wei.to_string()).map_err(|e| e.to_string());
+    }
+    match Chain::from_id(chain_id) {
+        Some(chain) => units::parse_native_amount(s, chain),
+        None => units::parse_amount(s),
+    }
+    .map(|wei| wei.to_string())
+    .map_err(|e| e.to_string())
+}
+
+/// [`parse_wei`] for the optional value of `flag`.
+pub(crate) fn wei_arg(flag: &str, value: Option<String>, chain_id: u64) -> Result<Option<String>> {
+    value.map(|v| parse_wei(&v, chain_id).map_err(|e| eyre!("invalid {flag}: {e}"))).transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_wei_accepts_units_decimal_and_hex() {
+        assert_eq!(parse_wei("0.1ether", 8453).unwrap(), "100000000000000000");
+        assert_eq!(parse_wei("2 gwei", 8453).unwrap(), "2000000000");
+        assert_eq!(parse_wei("21000", 8453).unwrap(), "21000");
+        assert_eq!(parse_wei("0xde0b6b3a7640000", 8453).unwrap(), "1000000000000000000");
+        assert_eq!(parse_wei(" 0X10 ", 8453).unwrap(), "16");
+        assert!(parse_wei("0x", 8453).is_err());
+        assert!(parse_wei("0xzz", 8453).is_err());
+    }
+
+    #[test]
+    fn parse_wei_checks_the_native_symbol() {
+        assert_eq!(parse_wei("0.1eth", 8453).unwrap(), "100000000000000000");
+        assert_eq!(parse_wei("1 POL", 137).unwrap(), "1000000000000000000");
+        assert!(parse_wei("1pol", 8453).unwrap_err().contains("not the native token of Base"));
+        assert!(parse_wei("1 eth", 137).is_err());
+        assert!(parse_wei("1 eth", 10).is_err());
+        assert_eq!(parse_wei("1 ether", 10).unwrap(), "1000000000000000000");
+    }
+}
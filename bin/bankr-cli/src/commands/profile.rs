@@ -0,0 +1,146 @@
+use std::path::Path;
+
+use clap::Subcommand;
+use eyre::{Result, eyre};
+use serde_json::json;
+
+use crate::{
+    config::{self, DEFAULT_PROFILE, Profile},
+    display, print_json,
+};
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum ProfileCommands {
+    /// List profiles, marking the active one.
+    List,
+
+    /// Add a named profile.
+    Add {
+        /// Profile name.
+        name: String,
+
+        /// API key to store (not verified; use `login` to verify).
+        #[arg(long)]
+        key: Option<String>,
+
+        /// Base URL for the Bankr Agent API.
+        #[arg(long)]
+        url: Option<String>,
+
+        /// Separate LLM Gateway key.
+        #[arg(long)]
+        llm_key: Option<String>,
+    },
+
+    /// Remove a named profile.
+    #[command(alias = "rm")]
+    Remove {
+        /// Profile name.
+        name: String,
+    },
+
+    /// Make a profile the default for later commands.
+    Use {
+        /// Profile name (`default` for the top-level settings).
+        name: String,
+    },
+}
+
+/// `bankr-cli profile <list|add|remove|use>`
+pub(crate) fn cmd_profile(
+    config_path: &Path,
+    command: &ProfileCommands,
+    selected: Option<&str>,
+    raw: bool,
+) -> Result<()> {
+    let mut cfg = config::load(config_path)?;
+
+    match command {
+        ProfileCommands::List => {
+            let active = cfg.active_profile_name(selected);
+            let profiles: Vec<_> =
+                cfg.profile_names().filter_map(|name| Some((name, cfg.profile(name)?))).collect();
+            if raw {
+                let list: Vec<_> = profiles
+                    .iter()
+                    .map(|(name, profile)| {
+                        json!({
+                            "name": name,
+                            "active": *name == active,
+                            "apiKey": profile.api_key.as_deref().map(config::mask_api_key),
+                            "encrypted": profile.encrypted_api_key.is_some(),
+                            "apiUrl": profile.api_url,
+                        })
+                    })
+                    .collect();
+                return print_json(&list, raw);
+            }
+            let width = profiles.iter().map(|(name, _)| name.len()).max().unwrap_or_default();
+            for (name, profile) in &profiles {
+                let marker = if *name == active { '*' } else { ' ' };
+                let key = match (&profile.encrypted_api_key, &profile.api_key) {
+                    (Some(_), _) => "Encrypted".to_owned(),
+                    (None, Some(key)) => config::mask_api_key(key),
+                    (None, None) => "Not set".to_owned(),
+                };
+                let url = profile.api_url.as_deref().unwrap_or("https://api.bankr.bot");
+                println!("{marker} {name:<width$}  {key:<13}  {url}");
+            }
+        }
+        ProfileCommands::Add { name, key, url, llm_key } => {
+            validate_name(name)?;
+            if cfg.profiles.contains_key(name) {
+                return Err(eyre!("profile `{name}` already exists"));
+            }
+            let profile = Profile {
+                api_key: key.clone(),
+                api_url: url.clone(),
+                llm_key: llm_key.clone(),
+                ..Profile::default()
+            };
+            cfg.profiles.insert(name.clone(), profile);
+            config::save(config_path, &cfg)?;
+            display::success(&format!("Added profile {name}"));
+            if key.is_none() {
+                println!("Run `bankr-cli --profile {name} login` to store its API key.");
+            }
+        }
+        ProfileCommands::Remove { name } => {
+            if name == DEFAULT_PROFILE {
+                return Err(eyre!("the `{DEFAULT_PROFILE}` profile cannot be removed"));
+            }
+            cfg.profiles.remove(name).ok_or_else(|| config::unknown_profile(name))?;
+            if cfg.default_profile.as_deref() == Some(name) {
+                cfg.default_profile = None;
+            }
+            config::save(config_path, &cfg)?;
+            display::success(&format!("Removed profile {name}"));
+        }
+        ProfileCommands::Use { name } => {
+            if cfg.profile(name).is_none() {
+                return Err(config::unknown_profile(name));
+            }
+            cfg.default_profile = (name != DEFAULT_PROFILE).then(|| name.clone());
+            config::save(config_path, &cfg)?;
+            display::success(&format!("Now using profile {name}"));
+        }
+    }
+    Ok(())
+}
+
+/// Profile names are used on the command line and as JSON keys.
+pub(crate) fn validate_name(name: &str) -> Result<()> {
+    if name == DEFAULT_PROFILE {
+        return Err(eyre!(
+            "`{DEFAULT_PROFILE}` is the built-in profile; use `login` to configure it"
+        ));
+    }
+    let valid = !name.is_empty() &&
+        name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !valid {
+        return Err(eyre!(
+            "invalid profile name `{name}`: use letters, digits, `-`, `_` and `.` only"
+        ));
+    }
+    Ok(())
+}
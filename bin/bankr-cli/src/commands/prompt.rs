@@ -1,6 +1,11 @@
 use std::time::Duration;
 
-use bankr_agent_api::{BankrAgentClient, types::PromptRequest};
+use bankr_agent_api::{
+    BankrAgentClient,
+    error::{BankrError, CancelOutcome},
+    poll::PollConfig,
+    types::PromptRequest,
+};
 use eyre::{Result, eyre};
 
 use crate::print_json;
@@ -17,14 +22,52 @@ pub(crate) async fn cmd_prompt(
     let req =
         PromptRequest { prompt: prompt.to_owned(), thread_id: thread_id.map(ToOwned::to_owned) };
 
-    if wait {
-        let job = client
-            .prompt_and_wait_with(&req, Duration::from_secs(poll_interval), max_attempts)
-            .await
-            .map_err(|e| eyre!("{e}"))?;
-        print_json(&job, raw)
-    } else {
+    if !wait {
         let resp = client.submit_prompt(&req).await.map_err(|e| eyre!("{e}"))?;
-        print_json(&resp, raw)
+        return print_json(&resp, raw);
+    }
+
+    // Listen for Ctrl-C before submitting, so an interrupt that arrives while
+    // the prompt is in flight still cancels the job instead of killing the
+    // process.
+    let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+    let listener = tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            let _ = tx.send(());
+        }
+    });
+    let interrupted = async {
+        if rx.await.is_err() {
+            // No signal handler could be installed: never interrupt.
+            std::future::pending::<()>().await;
+        }
+    };
+
+    let resp = client.submit_prompt(&req).await.map_err(|e| eyre!("{e}"))?;
+    let result = client
+        .poll_job_or_cancel(
+            &resp.job_id,
+            &PollConfig::fixed(Duration::from_secs(poll_interval), max_attempts),
+            interrupted,
+        )
+        .await;
+    listener.abort();
+
+    match result {
+        Ok(job) => print_json(&job, raw),
+        Err(BankrError::Interrupted { job_id, outcome }) => match outcome {
+            CancelOutcome::Cancelled => Err(eyre!("Interrupted: job {job_id} was cancelled")),
+            CancelOutcome::AlreadyFinished(job) => {
+                print_json(&job, raw)?;
+                Err(eyre!(
+                    "Interrupted: job {job_id} had already {} and was not cancelled",
+                    job.status
+                ))
+            }
+            CancelOutcome::CancelFailed(e) => Err(eyre!(
+                "Interrupted: failed to cancel job {job_id} ({e}); it may still be running"
+            )),
+        },
+        Err(e) => Err(eyre!("{e}")),
     }
 }
@@ -1,9 +1,13 @@
 use bankr_agent_api::{
     BankrAgentClient,
-    types::{EvmTransaction, SignRequest, SignatureType},
+    chain::Chain,
+    primitives::{Address, Bytes},
+    transaction::SignedTransaction,
+    types::{EvmTransaction, SignRequest, TypedData},
+    verify,
 };
-use clap::Subcommand;
-use eyre::{Result, WrapErr, eyre};
+use clap::{Args, Subcommand};
+use eyre::{Result, WrapErr, bail, eyre};
 
 use crate::print_json;
 
@@ -27,11 +31,13 @@ pub(crate) enum SignCommands {
         #[arg(long)]
         to: String,
 
-        /// Chain ID.
-        #[arg(long)]
-        chain_id: u64,
+        /// Chain name or ID (e.g. `base` or 8453, `eth` or 1; default:
+        /// `defaultChain`).
+        #[arg(long = "chain", visible_alias = "chain-id", value_parser = super::parse_chain)]
+        chain_id: Option<u64>,
 
-        /// Value in wei.
+        /// Value, e.g. `0.1ether`, `0.1eth` (the chain's native token),
+        /// `25gwei` or a bare integer in wei.
         #[arg(long)]
         value: Option<String>,
 
@@ -39,48 +45,130 @@ pub(crate) enum SignCommands {
         #[arg(long)]
         data: Option<String>,
     },
+
+    /// Verify a signature offline and print the recovered signer.
+    Verify(VerifyArgs),
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct VerifyArgs {
+    #[command(subcommand)]
+    kind: VerifyCommands,
+
+    /// Fail unless the signer is this address.
+    #[arg(long, global = true)]
+    expect: Option<String>,
+
+    /// Fail unless the signer is the EVM wallet of the authenticated account
+    /// (requires an API key).
+    #[arg(long, global = true, default_value_t = false)]
+    pub(crate) check_wallet: bool,
+}
+
+#[derive(Debug, Subcommand)]
+enum VerifyCommands {
+    /// Verify a personal_sign signature over a text message.
+    Personal {
+        /// The message that was signed.
+        message: String,
+
+        /// The signature (65-byte hex).
+        #[arg(long)]
+        signature: String,
+    },
+
+    /// Verify an eth_signTypedData_v4 signature.
+    TypedData {
+        /// JSON string of the typed data object.
+        typed_data_json: String,
+
+        /// The signature (65-byte hex).
+        #[arg(long)]
+        signature: String,
+    },
+
+    /// Decode a signed raw transaction and recover its sender.
+    Transaction {
+        /// The signed transaction (hex), as returned by `sign transaction`.
+        raw_tx: String,
+    },
 }
 
 pub(crate) async fn cmd_sign(
     client: &BankrAgentClient,
     kind: SignCommands,
+    default_chain: Option<u64>,
     raw: bool,
 ) -> Result<()> {
     let req = match kind {
-        SignCommands::Personal { message } => SignRequest {
-            signature_type: SignatureType::PersonalSign,
-            message: Some(message),
-            typed_data: None,
-            transaction: None,
-        },
-        SignCommands::TypedData { typed_data_json } => {
-            let typed_data: serde_json::Value =
-                serde_json::from_str(&typed_data_json).wrap_err("Invalid typed-data JSON")?;
-            SignRequest {
-                signature_type: SignatureType::EthSignTypedDataV4,
-                message: None,
-                typed_data: Some(typed_data),
-                transaction: None,
-            }
-        }
-        SignCommands::Transaction { to, chain_id, value, data } => SignRequest {
-            signature_type: SignatureType::EthSignTransaction,
-            message: None,
-            typed_data: None,
-            transaction: Some(EvmTransaction {
-                to,
-                chain_id,
-                value,
+        SignCommands::Verify(args) => return cmd_verify(Some(client), &args, raw).await,
+        SignCommands::Personal { message } => SignRequest::personal(message),
+        SignCommands::TypedData { typed_data_json } => SignRequest::typed_data(
+            serde_json::from_str(&typed_data_json).wrap_err("Invalid typed-data JSON")?,
+        ),
+        SignCommands::Transaction { to, chain_id, value, data } => {
+            let chain_id = super::resolve_chain(chain_id, default_chain)?;
+            SignRequest::transaction(EvmTransaction {
+                value: super::wei_arg("--value", value, chain_id)?,
                 data,
-                gas: None,
-                gas_price: None,
-                max_fee_per_gas: None,
-                max_priority_fee_per_gas: None,
-                nonce: None,
-            }),
-        },
+                ..EvmTransaction::new(to, chain_id)
+            })
+        }
     };
 
     let resp = client.sign(&req).await.map_err(|e| eyre!("{e}"))?;
     print_json(&resp, raw)
 }
+
+/// Recover the signer described by `args`, check it against `--expect` and
+/// `--check-wallet`, and print it. `client` is only used for `--check-wallet`.
+pub(crate) async fn cmd_verify(
+    client: Option<&BankrAgentClient>,
+    args: &VerifyArgs,
+    raw: bool,
+) -> Result<()> {
+    let hex = |s: &str| s.parse::<Bytes>().wrap_err("Invalid hex");
+    let mut output = serde_json::Map::new();
+    let signer = match &args.kind {
+        VerifyCommands::Personal { message, signature } => {
+            verify::recover_personal_sign(message.as_bytes(), hex(signature)?.as_slice())?
+        }
+        VerifyCommands::TypedData { typed_data_json, signature } => {
+            let data: TypedData =
+                serde_json::from_str(typed_data_json).wrap_err("Invalid typed-data JSON")?;
+            verify::recover_typed_data(&data, hex(signature)?.as_slice())?
+        }
+        VerifyCommands::Transaction { raw_tx } => {
+            let tx: SignedTransaction = raw_tx.parse()?;
+            let hash = Bytes::from(&tx.hash()[..]).to_string();
+            if let Some(chain) = tx.chain_id.and_then(Chain::from_id) {
+                output.insert("explorerUrl".to_owned(), chain.tx_url(&hash).into());
+            }
+            output.insert("hash".to_owned(), hash.into());
+            output.insert("transaction".to_owned(), serde_json::to_value(&tx)?);
+            tx.recover_sender()?
+        }
+    };
+
+    if let Some(expect) = &args.expect {
+        let expect: Address = expect.parse().wrap_err("Invalid --expect address")?;
+        if expect != signer {
+            bail!("Signer {signer} does not match expected {expect}");
+        }
+    }
+    if args.check_wallet {
+        let client = client.ok_or_else(|| eyre!("--check-wallet requires an API key"))?;
+        let me = client.get_me().await.map_err(|e| eyre!("{e}"))?;
+        let wallet = me
+            .wallets
+            .iter()
+            .find_map(|w| w.evm_address())
+            .ok_or_else(|| eyre!("Account has no EVM wallet"))?;
+        if wallet != signer {
+            bail!("Signer {signer} is not the account's EVM wallet {wallet}");
+        }
+    }
+
+    output.insert("signer".to_owned(), signer.to_string().into());
+    print_json(&output, raw)
+}
@@ -1,10 +1,12 @@
 use bankr_agent_api::{
     BankrAgentClient,
+    chain::Chain,
     types::{EvmTransaction, SubmitRequest},
 };
 use eyre::{Result, eyre};
 
-use crate::print_json;
+use super::wei_arg;
+use crate::{display, print_json};
 
 #[expect(clippy::too_many_arguments)]
 pub(crate) async fn cmd_submit(
@@ -21,22 +23,31 @@ pub(crate) async fn cmd_submit(
     no_wait: bool,
     raw: bool,
 ) -> Result<()> {
+    if Chain::from_id(chain_id).is_none() {
+        display::warning(&format!("chain ID {chain_id} is not supported by the submit endpoint"));
+    }
     let req = SubmitRequest {
         transaction: EvmTransaction {
-            to: to.to_owned(),
-            chain_id,
-            value,
+            value: wei_arg("--value", value, chain_id)?,
             data,
             gas,
-            gas_price: None,
-            max_fee_per_gas,
-            max_priority_fee_per_gas,
+            max_fee_per_gas: wei_arg("--max-fee-per-gas", max_fee_per_gas, chain_id)?,
+            max_priority_fee_per_gas: wei_arg(
+                "--max-priority-fee-per-gas",
+                max_priority_fee_per_gas,
+                chain_id,
+            )?,
             nonce,
+            ..EvmTransaction::new(to, chain_id)
         },
         description,
         wait_for_confirmation: Some(!no_wait),
     };
 
     let resp = client.submit_transaction(&req).await.map_err(|e| eyre!("{e}"))?;
-    print_json(&resp, raw)
+    let mut output = serde_json::to_value(&resp)?;
+    if let (Some(url), Some(map)) = (resp.explorer_url(), output.as_object_mut()) {
+        map.insert("explorerUrl".to_owned(), url.into());
+    }
+    print_json(&output, raw)
 }
@@ -9,6 +9,7 @@ pub(crate) async fn cmd_whoami(
     client: &BankrAgentClient,
     raw: bool,
     config_path: &Path,
+    profile: &str,
     api_key: &str,
     api_url: &str,
 ) -> Result<()> {
@@ -16,7 +17,7 @@ pub(crate) async fn cmd_whoami(
     if raw {
         print_json(&resp, raw)
     } else {
-        display::print_whoami(config_path, api_key, api_url, &resp);
+        display::print_whoami(config_path, profile, api_key, api_url, &resp);
         Ok(())
     }
 }
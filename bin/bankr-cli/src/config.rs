@@ -2,23 +2,160 @@
 //!
 //! Loads, saves, and resolves configuration from `$HOME/.bankr/config.json`,
 //! matching the format used by the npm CLI (camelCase JSON keys).
+//!
+//! The file may be shared with the npm CLI, so keys this crate does not know
+//! are kept as they are, and a file that fails to parse is never overwritten.
+//! Commands that only read the configuration warn about such a file and use
+//! the defaults instead (see [`load_or_default`]).
+
+use std::{
+    collections::BTreeMap,
+    ffi::OsString,
+    fmt, fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    str::FromStr,
+    sync::{Mutex, PoisonError},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bankr_agent_api::chain::{Chain, UnknownChain};
+use eyre::{Result, WrapErr, eyre};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use zeroize::Zeroizing;
 
-use std::path::{Path, PathBuf};
+use crate::{
+    display,
+    keystore::{self, EncryptedKey},
+};
 
-use eyre::{Result, WrapErr};
-use serde::{Deserialize, Serialize};
+/// Name of the profile stored at the top level of the config file.
+pub const DEFAULT_PROFILE: &str = "default";
 
 /// Bankr CLI configuration, persisted as JSON in `$HOME/.bankr/config.json`.
+///
+/// The top-level keys are the `default` profile, which is what the npm CLI
+/// reads; other named profiles live under `profiles`.
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BankrConfig {
+    /// Settings of the `default` profile.
+    #[serde(flatten)]
+    pub default: Profile,
+
+    /// Profile used when neither `--profile` nor `BANKR_PROFILE` is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_profile: Option<String>,
+
+    /// Named profiles other than `default`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// Settings for one Bankr account.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Profile {
     /// API key for authenticating with the Bankr Agent API.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub api_key: Option<String>,
 
+    /// API key sealed with a passphrase (`login --encrypt`), used instead of
+    /// `apiKey`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encrypted_api_key: Option<EncryptedKey>,
+
     /// Base URL for the Bankr Agent API.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub api_url: Option<String>,
+
+    /// Shell command whose output is the API key (e.g. `pass show bankr`),
+    /// used instead of `apiKey` so the key need not be stored in the file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_command: Option<String>,
+
+    /// Separate key for the LLM Gateway. When unset the API key is used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub llm_key: Option<String>,
+
+    /// Base URL for the LLM Gateway.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub llm_url: Option<String>,
+
+    /// Chain ID used by `submit` and `sign transaction` without `--chain`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_chain: Option<u64>,
+
+    /// Seconds between job status polls for `prompt`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub poll_interval: Option<u64>,
+
+    /// Maximum number of job status polls for `prompt`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_poll_attempts: Option<u32>,
+
+    /// How JSON results are printed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_format: Option<OutputFormat>,
+
+    /// Keys not modelled above (e.g. written by the npm CLI), preserved in
+    /// their original order when the file is saved.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// How JSON results are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Indented JSON (the default).
+    Pretty,
+    /// Compact JSON, as with `--raw`.
+    Raw,
+}
+
+impl BankrConfig {
+    /// Name of the active profile: `selected` (from `--profile` or
+    /// `BANKR_PROFILE`), else `defaultProfile`, else `default`.
+    pub fn active_profile_name<'a>(&'a self, selected: Option<&'a str>) -> &'a str {
+        selected.or(self.default_profile.as_deref()).unwrap_or(DEFAULT_PROFILE)
+    }
+
+    /// The active profile and its name.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the selected profile does not exist.
+    pub fn active_profile<'a>(
+        &'a self,
+        selected: Option<&'a str>,
+    ) -> Result<(&'a str, &'a Profile)> {
+        let name = self.active_profile_name(selected);
+        let profile = self.profile(name).ok_or_else(|| unknown_profile(name))?;
+        Ok((name, profile))
+    }
+
+    /// The profile called `name`, if it exists.
+    pub fn profile(&self, name: &str) -> Option<&Profile> {
+        if name == DEFAULT_PROFILE { Some(&self.default) } else { self.profiles.get(name) }
+    }
+
+    /// The profile called `name`, for editing.
+    pub fn profile_mut(&mut self, name: &str) -> Option<&mut Profile> {
+        if name == DEFAULT_PROFILE { Some(&mut self.default) } else { self.profiles.get_mut(name) }
+    }
+
+    /// Names of all profiles, `default` first.
+    pub fn profile_names(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(DEFAULT_PROFILE).chain(self.profiles.keys().map(String::as_str))
+    }
+}
+
+/// Error for a profile name that is not in the config file.
+pub fn unknown_profile(name: &str) -> eyre::Report {
+    eyre!("profile `{name}` not found. Run `bankr-cli profile list` to see configured profiles.")
 }
 
 /// Returns the default config file path: `$HOME/.bankr/config.json`.
@@ -32,40 +169,162 @@ pub fn default_config_path() -> Option<PathBuf> {
 
 /// Load configuration from `path`.
 ///
-/// Returns [`BankrConfig::default()`] if the file does not exist or cannot be
-/// parsed.
-pub fn load(path: &Path) -> BankrConfig {
-    let Ok(contents) = std::fs::read_to_string(path) else {
-        return BankrConfig::default();
+/// Returns [`BankrConfig::default()`] if the file does not exist or is empty.
+///
+/// # Errors
+///
+/// Fails if the file cannot be read or is not valid configuration JSON, so
+/// that a later [`save`] cannot replace it.
+pub fn load(path: &Path) -> Result<BankrConfig> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(BankrConfig::default()),
+        Err(e) => {
+            return Err(e)
+                .wrap_err_with(|| format!("failed to read config file {}", path.display()));
+        }
     };
+    if contents.trim().is_empty() {
+        return Ok(BankrConfig::default());
+    }
+
+    serde_json::from_str(&contents).map_err(|e| {
+        eyre!("invalid config file {}: {e}. Fix or remove it and try again.", path.display())
+    })
+}
 
-    serde_json::from_str(&contents).unwrap_or_default()
+/// Like [`load`], but a file that cannot be read or parsed only produces a
+/// warning and [`BankrConfig::default()`].
+///
+/// For commands that never write the file, so that e.g. `--api-key` and
+/// `--base-url` keep working while the file is broken.
+pub fn load_or_default(path: &Path) -> BankrConfig {
+    load(path).unwrap_or_else(|e| {
+        display::warning(&format!("{e:#}; using default settings"));
+        BankrConfig::default()
+    })
 }
 
+/// Config keys holding credentials.
+const SECRET_KEYS: [&str; 3] = ["apiKey", "encryptedApiKey", "llmKey"];
+
 /// Persist `config` as pretty-printed JSON to `path`.
 ///
-/// Creates parent directories as needed and sets `0600` permissions on Unix.
+/// Creates parent directories as needed. If `path` is a symlink, its target
+/// is written instead of replacing the link. The file is written to a
+/// uniquely named temporary file next to the target and renamed into place,
+/// so it is never left half written; the previous contents are kept as
+/// `<path>.bak`, minus any credential that `config` removes or replaces (so
+/// `logout` and `login --encrypt` leave no key behind). Both files get
+/// `0600` permissions on Unix.
 pub fn save(path: &Path, config: &BankrConfig) -> Result<()> {
+    let path = &resolve_symlinks(path)?;
     if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent)
+        fs::create_dir_all(parent)
             .wrap_err_with(|| format!("failed to create config directory {}", parent.display()))?;
     }
 
-    let json =
-        serde_json::to_string_pretty(config).wrap_err("failed to serialize configuration")?;
+    let new = serde_json::to_value(config).wrap_err("failed to serialize configuration")?;
+    let mut json =
+        serde_json::to_string_pretty(&new).wrap_err("failed to serialize configuration")?;
+    json.push('\n');
+
+    match fs::read(path) {
+        Ok(old) => {
+            let backup = sibling(path, ".bak");
+            let mut old = old;
+            if let Ok(mut value) = serde_json::from_slice::<Value>(&old) &&
+                scrub_removed_secrets(&mut value, &new)
+            {
+                old = serde_json::to_vec_pretty(&value)
+                    .wrap_err("failed to serialize configuration")?;
+                old.push(b'\n');
+            }
+            replace_private(&backup, &old).wrap_err_with(|| {
+                format!("failed to back up config file to {}", backup.display())
+            })?;
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => {
+            return Err(e)
+                .wrap_err_with(|| format!("failed to read config file {}", path.display()));
+        }
+    }
 
-    std::fs::write(path, &json)
-        .wrap_err_with(|| format!("failed to write config file {}", path.display()))?;
+    replace_private(path, json.as_bytes())
+        .wrap_err_with(|| format!("failed to write config file {}", path.display()))
+}
 
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        let perms = std::fs::Permissions::from_mode(0o600);
-        std::fs::set_permissions(path, perms)
-            .wrap_err_with(|| format!("failed to set permissions on {}", path.display()))?;
+/// Remove the credentials from the previous config `old` that are not in
+/// `new` with the same value. Returns whether anything was removed.
+fn scrub_removed_secrets(old: &mut Value, new: &Value) -> bool {
+    fn scrub(old: &mut Value, new: &Value) -> bool {
+        let Some(old) = old.as_object_mut() else { return false };
+        let len = old.len();
+        old.retain(|key, value| {
+            !SECRET_KEYS.contains(&key.as_str()) || new.get(key) == Some(value)
+        });
+        old.len() != len
     }
 
-    Ok(())
+    let mut scrubbed = scrub(old, new);
+    if let Some(profiles) = old.get_mut("profiles").and_then(Value::as_object_mut) {
+        for (name, profile) in profiles {
+            scrubbed |= scrub(profile, &new["profiles"][name]);
+        }
+    }
+    scrubbed
+}
+
+/// Follow `path` through any chain of symlinks, even if the final target
+/// does not exist yet.
+fn resolve_symlinks(path: &Path) -> Result<PathBuf> {
+    // The same limit as Linux's `MAXSYMLINKS`.
+    const MAX_LINKS: usize = 40;
+
+    let mut path = path.to_owned();
+    for _ in 0..MAX_LINKS {
+        match fs::symlink_metadata(&path) {
+            Ok(meta) if meta.file_type().is_symlink() => {
+                let target = fs::read_link(&path).wrap_err_with(|| {
+                    format!("failed to read config symlink {}", path.display())
+                })?;
+                path = path.parent().map_or_else(|| target.clone(), |dir| dir.join(&target));
+            }
+            _ => return Ok(path),
+        }
+    }
+    Err(eyre!("too many levels of symlinks at config file {}", path.display()))
+}
+
+/// `path` with `suffix` appended to its file name, e.g. `config.json.bak`.
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().map(OsString::from).unwrap_or_default();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// Atomically replace `path` with `contents`, via a uniquely named temporary
+/// file in the same directory.
+fn replace_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().subsec_nanos();
+    let tmp = sibling(path, &format!(".{}.{nanos}.tmp", std::process::id()));
+    write_private(&tmp, contents)?;
+    fs::rename(&tmp, path).inspect_err(|_| {
+        let _ = fs::remove_file(&tmp);
+    })
+}
+
+/// Write `contents` to a new file at `path` readable only by the owner, and
+/// flush it to disk. Fails if `path` already exists.
+fn write_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(path)?;
+    file.write_all(contents)?;
+    file.sync_all()
 }
 
 /// Mask an API key for display, e.g. `bk_WKW...46ZE`.
@@ -78,13 +337,294 @@ pub fn mask_api_key(key: &str) -> String {
     format!("{}...{}", &key[..6], &key[key.len() - 4..])
 }
 
-/// Resolve the API key with priority: CLI flag > environment variable > config file.
+/// Resolve the API key with priority: CLI flag > environment variable >
+/// `--api-key-command` > profile `apiKeyCommand` > `encryptedApiKey` >
+/// `apiKey`.
+///
+/// # Errors
+///
+/// Fails if the key command cannot be run, exits unsuccessfully or prints
+/// nothing, or if the encrypted key cannot be unlocked.
 pub fn resolve_api_key(
     cli_flag: Option<&str>,
     env_var: Option<&str>,
-    config: &BankrConfig,
-) -> Option<String> {
-    cli_flag.or(env_var).map(String::from).or_else(|| config.api_key.clone())
+    key_command: Option<&str>,
+    profile: &Profile,
+) -> Result<Option<Zeroizing<String>>> {
+    if let Some(key) = cli_flag.or(env_var) {
+        return Ok(Some(Zeroizing::new(key.to_owned())));
+    }
+    if let Some(command) = key_command.or(profile.api_key_command.as_deref()) {
+        return run_key_command(command).map(|key| Some(Zeroizing::new(key)));
+    }
+    if let Some(sealed) = &profile.encrypted_api_key {
+        return keystore::unlock(sealed).map(Some);
+    }
+    Ok(profile.api_key.clone().map(Zeroizing::new))
+}
+
+/// Resolve the LLM Gateway key with priority: CLI flag / `BANKR_LLM_KEY` >
+/// profile `llmKey` > the Agent API key (see [`resolve_api_key`]), since a
+/// single key can serve both when `llmGatewayEnabled` is set.
+///
+/// # Errors
+///
+/// Fails if the API key has to come from a key command or encrypted key and
+/// that fails.
+pub fn resolve_llm_key(
+    llm_key: Option<&str>,
+    api_key: Option<&str>,
+    key_command: Option<&str>,
+    profile: &Profile,
+) -> Result<Option<Zeroizing<String>>> {
+    match llm_key.map(String::from).or_else(|| profile.llm_key.clone()) {
+        Some(key) => Ok(Some(Zeroizing::new(key))),
+        None => resolve_api_key(api_key, None, key_command, profile),
+    }
+}
+
+/// Run `command` through the shell and return the first line of its output,
+/// trimmed, like a git credential helper. The command's stdin and stderr are
+/// the terminal's, so it can prompt.
+///
+/// Results are cached per command for the lifetime of the process, so a
+/// helper runs (and prompts) at most once.
+///
+/// This blocks the calling thread until the command exits, while holding the
+/// cache's `std::sync::Mutex`, even when called from async code. That is
+/// fine for the CLI: keys are resolved once, before any request is made,
+/// and the wait is usually on the user typing at the helper's prompt.
+///
+/// # Errors
+///
+/// Fails if the command cannot be started, exits unsuccessfully or prints no
+/// key.
+pub fn run_key_command(command: &str) -> Result<String> {
+    static CACHE: Mutex<BTreeMap<String, String>> = Mutex::new(BTreeMap::new());
+
+    let mut cache = CACHE.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(key) = cache.get(command) {
+        return Ok(key.clone());
+    }
+
+    let (shell, flag) = if cfg!(windows) { ("cmd", "/C") } else { ("sh", "-c") };
+    let output = Command::new(shell)
+        .args([flag, command])
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .output()
+        .wrap_err_with(|| format!("failed to run apiKeyCommand `{command}`"))?;
+    if !output.status.success() {
+        return Err(eyre!("apiKeyCommand `{command}` failed ({})", output.status));
+    }
+    let stdout = String::from_utf8(output.stdout)
+        .map_err(|_| eyre!("apiKeyCommand `{command}` printed invalid UTF-8"))?;
+    let key = stdout.lines().next().unwrap_or_default().trim();
+    if key.is_empty() {
+        return Err(eyre!("apiKeyCommand `{command}` printed no key"));
+    }
+
+    cache.insert(command.to_owned(), key.to_owned());
+    Ok(key.to_owned())
+}
+
+// ---------------------------------------------------------------------------
+// Typed keys for `config get/set/unset`
+// ---------------------------------------------------------------------------
+
+/// A profile setting that `bankr-cli config get/set/unset` can change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigKey {
+    /// `apiKey`
+    ApiKey,
+    /// `apiUrl`
+    ApiUrl,
+    /// `apiKeyCommand`
+    ApiKeyCommand,
+    /// `llmKey`
+    LlmKey,
+    /// `llmUrl`
+    LlmUrl,
+    /// `defaultChain`
+    DefaultChain,
+    /// `pollInterval`
+    PollInterval,
+    /// `maxPollAttempts`
+    MaxPollAttempts,
+    /// `outputFormat`
+    OutputFormat,
+}
+
+impl ConfigKey {
+    /// Every key, in display order.
+    pub const ALL: [Self; 9] = [
+        Self::ApiKey,
+        Self::ApiUrl,
+        Self::ApiKeyCommand,
+        Self::LlmKey,
+        Self::LlmUrl,
+        Self::DefaultChain,
+        Self::PollInterval,
+        Self::MaxPollAttempts,
+        Self::OutputFormat,
+    ];
+
+    /// Name of the key in the config file.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::ApiKey => "apiKey",
+            Self::ApiUrl => "apiUrl",
+            Self::ApiKeyCommand => "apiKeyCommand",
+            Self::LlmKey => "llmKey",
+            Self::LlmUrl => "llmUrl",
+            Self::DefaultChain => "defaultChain",
+            Self::PollInterval => "pollInterval",
+            Self::MaxPollAttempts => "maxPollAttempts",
+            Self::OutputFormat => "outputFormat",
+        }
+    }
+
+    /// Whether values are masked on output.
+    pub const fn is_secret(self) -> bool {
+        matches!(self, Self::ApiKey | Self::LlmKey)
+    }
+
+    /// The value stored in `profile`, in the form [`set`](Self::set) accepts.
+    pub fn get(self, profile: &Profile) -> Option<String> {
+        match self {
+            Self::ApiKey => profile.api_key.clone(),
+            Self::ApiUrl => profile.api_url.clone(),
+            Self::ApiKeyCommand => profile.api_key_command.clone(),
+            Self::LlmKey => profile.llm_key.clone(),
+            Self::LlmUrl => profile.llm_url.clone(),
+            Self::DefaultChain => profile.default_chain.map(|id| {
+                Chain::from_id(id)
+                    .map_or_else(|| id.to_string(), |c| c.info().aliases[0].to_owned())
+            }),
+            Self::PollInterval => profile.poll_interval.map(|s| s.to_string()),
+            Self::MaxPollAttempts => profile.max_poll_attempts.map(|n| n.to_string()),
+            Self::OutputFormat => profile.output_format.map(|f| match f {
+                OutputFormat::Pretty => "pretty".to_owned(),
+                OutputFormat::Raw => "raw".to_owned(),
+            }),
+        }
+    }
+
+    /// Like [`get`](Self::get), with secrets masked.
+    pub fn display(self, profile: &Profile) -> Option<String> {
+        let value = self.get(profile)?;
+        Some(if self.is_secret() { mask_api_key(&value) } else { value })
+    }
+
+    /// Validate `value` and store it in `profile`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `value` is not valid for this key.
+    pub fn set(self, profile: &mut Profile, value: &str) -> Result<()> {
+        let value = value.trim();
+        let invalid =
+            |expected: &str| eyre!("invalid value `{value}` for {}: {expected}", self.name());
+        match self {
+            Self::ApiKey | Self::LlmKey => {
+                if value.is_empty() || value.contains(char::is_whitespace) {
+                    return Err(invalid("expected a non-empty key without spaces"));
+                }
+                if self == Self::ApiKey {
+                    // A plain key replaces an encrypted one rather than
+                    // being shadowed by it.
+                    profile.encrypted_api_key = None;
+                    profile.api_key = Some(value.to_owned());
+                } else {
+                    profile.llm_key = Some(value.to_owned());
+                }
+            }
+            Self::ApiUrl | Self::LlmUrl => {
+                let rest = value.strip_prefix("https://").or_else(|| value.strip_prefix("http://"));
+                if rest.is_none_or(|host| host.is_empty() || host.contains(char::is_whitespace)) {
+                    return Err(invalid("expected an http:// or https:// URL"));
+                }
+                let slot =
+                    if self == Self::ApiUrl { &mut profile.api_url } else { &mut profile.llm_url };
+                *slot = Some(value.trim_end_matches('/').to_owned());
+            }
+            Self::ApiKeyCommand => {
+                // `value` is trimmed above, so this also rejects a command
+                // that is only whitespace.
+                if value.is_empty() {
+                    return Err(invalid("expected a shell command"));
+                }
+                profile.api_key_command = Some(value.to_owned());
+            }
+            Self::DefaultChain => {
+                let chain: Chain =
+                    value.parse().map_err(|e: UnknownChain| invalid(&e.to_string()))?;
+                profile.default_chain = Some(chain.id());
+            }
+            Self::PollInterval => {
+                let secs: u64 = value.parse().map_err(|_| invalid("expected whole seconds"))?;
+                if secs > 3600 {
+                    return Err(invalid("expected at most 3600 seconds"));
+                }
+                profile.poll_interval = Some(secs);
+            }
+            Self::MaxPollAttempts => {
+                let n: u32 = value
+                    .parse()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or_else(|| invalid("expected a positive integer"))?;
+                profile.max_poll_attempts = Some(n);
+            }
+            Self::OutputFormat => {
+                profile.output_format = Some(match value.to_ascii_lowercase().as_str() {
+                    "pretty" => OutputFormat::Pretty,
+                    "raw" => OutputFormat::Raw,
+                    _ => return Err(invalid("expected `pretty` or `raw`")),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove the value from `profile`.
+    pub fn unset(self, profile: &mut Profile) {
+        match self {
+            Self::ApiKey => {
+                profile.api_key = None;
+                profile.encrypted_api_key = None;
+            }
+            Self::ApiUrl => profile.api_url = None,
+            Self::ApiKeyCommand => profile.api_key_command = None,
+            Self::LlmKey => profile.llm_key = None,
+            Self::LlmUrl => profile.llm_url = None,
+            Self::DefaultChain => profile.default_chain = None,
+            Self::PollInterval => profile.poll_interval = None,
+            Self::MaxPollAttempts => profile.max_poll_attempts = None,
+            Self::OutputFormat => profile.output_format = None,
+        }
+    }
+}
+
+impl fmt::Display for ConfigKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Case-insensitive; `-` and `_` are ignored, so `api-url` and `API_URL`
+/// both mean `apiUrl`.
+impl FromStr for ConfigKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalize = |s: &str| s.replace(['-', '_'], "").to_ascii_lowercase();
+        let wanted = normalize(s);
+        Self::ALL.into_iter().find(|key| normalize(key.name()) == wanted).ok_or_else(|| {
+            let names: Vec<_> = Self::ALL.iter().map(|key| key.name()).collect();
+            format!("unknown config key `{s}` (valid keys: {})", names.join(", "))
+        })
+    }
 }
 
 #[cfg(test)]
@@ -93,18 +633,35 @@ mod tests {
 
     use super::*;
 
+    fn profile(api_key: &str) -> Profile {
+        Profile { api_key: Some(api_key.to_owned()), ..Profile::default() }
+    }
+
+    fn key(key: &str) -> Option<Zeroizing<String>> {
+        Some(Zeroizing::new(key.to_owned()))
+    }
+
     #[test]
     fn round_trip_write_then_read() {
         let dir = tempfile::tempdir().unwrap();
         let path = dir.path().join("config.json");
 
         let original = BankrConfig {
-            api_key: Some("bk_test_key_12345".to_owned()),
-            api_url: Some("https://api.example.com".to_owned()),
+            default: Profile {
+                api_key: Some("bk_test_key_12345".to_owned()),
+                api_url: Some("https://api.example.com".to_owned()),
+                llm_key: Some("bk_llm_key_67890".to_owned()),
+                default_chain: Some(8453),
+                output_format: Some(OutputFormat::Raw),
+                extra: serde_json::json!({ "theme": "dark" }).as_object().cloned().unwrap(),
+                ..Profile::default()
+            },
+            default_profile: Some("trading".to_owned()),
+            profiles: BTreeMap::from([("trading".to_owned(), profile("bk_trading"))]),
         };
 
         save(&path, &original).unwrap();
-        let loaded = load(&path);
+        let loaded = load(&path).unwrap();
 
         assert_eq!(original, loaded);
     }
@@ -114,18 +671,95 @@ mod tests {
         let dir = tempfile::tempdir().unwrap();
         let path = dir.path().join("nonexistent.json");
 
-        let config = load(&path);
+        let config = load(&path).unwrap();
         assert_eq!(config, BankrConfig::default());
     }
 
     #[test]
-    fn load_malformed_json_returns_default() {
+    fn load_malformed_json_is_an_error() {
         let dir = tempfile::tempdir().unwrap();
         let path = dir.path().join("bad.json");
         fs::write(&path, "not json at all").unwrap();
+        assert!(load(&path).unwrap_err().to_string().contains("invalid config file"));
 
-        let config = load(&path);
-        assert_eq!(config, BankrConfig::default());
+        fs::write(&path, r#"{"apiKey": 42}"#).unwrap();
+        assert!(load(&path).is_err());
+        assert_eq!(load_or_default(&path), BankrConfig::default());
+    }
+
+    #[test]
+    fn unknown_keys_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        let original = r#"{"zeta":{"nested":[1,2]},"apiKey":"bk_old","alpha":true}"#;
+        fs::write(&path, original).unwrap();
+
+        let mut config = load(&path).unwrap();
+        config.default.api_key = Some("bk_new".to_owned());
+        save(&path, &config).unwrap();
+
+        let saved: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let keys: Vec<&str> = saved.as_object().unwrap().keys().map(String::as_str).collect();
+        assert_eq!(keys, ["apiKey", "zeta", "alpha"]);
+        assert_eq!(saved["zeta"]["nested"][1], 2);
+        assert_eq!(saved["apiKey"], "bk_new");
+    }
+
+    #[test]
+    fn save_keeps_backup_and_no_temp_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        fs::write(&path, r#"{"apiUrl":"http://old"}"#).unwrap();
+
+        let config = BankrConfig { default: profile("bk_new"), ..BankrConfig::default() };
+        save(&path, &config).unwrap();
+
+        assert_eq!(load(&path).unwrap(), config);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("config.json.bak")).unwrap(),
+            r#"{"apiUrl":"http://old"}"#
+        );
+        let mut files: Vec<_> =
+            fs::read_dir(dir.path()).unwrap().map(|e| e.unwrap().file_name()).collect();
+        files.sort();
+        assert_eq!(files, ["config.json", "config.json.bak"]);
+    }
+
+    #[test]
+    fn save_drops_replaced_credentials_from_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        fs::write(
+            &path,
+            r#"{"apiKey":"bk_old","llmKey":"bk_llm","profiles":{"work":{"apiKey":"bk_work"}}}"#,
+        )
+        .unwrap();
+
+        let mut config = load(&path).unwrap();
+        config.default.api_key = Some("bk_new".to_owned());
+        config.profiles.remove("work");
+        save(&path, &config).unwrap();
+
+        let backup = fs::read_to_string(dir.path().join("config.json.bak")).unwrap();
+        let backup: Value = serde_json::from_str(&backup).unwrap();
+        assert_eq!(backup, serde_json::json!({ "llmKey": "bk_llm", "profiles": { "work": {} } }));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn save_writes_through_symlink() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("dotfiles").join("bankr.json");
+        let link = dir.path().join("config.json");
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        let config = BankrConfig { default: profile("bk_new"), ..BankrConfig::default() };
+        save(&link, &config).unwrap();
+        save(&link, &config).unwrap();
+
+        assert!(fs::symlink_metadata(&link).unwrap().file_type().is_symlink());
+        assert_eq!(load(&target).unwrap(), config);
+        assert!(target.with_file_name("bankr.json.bak").exists());
     }
 
     #[test]
@@ -145,19 +779,32 @@ mod tests {
         let dir = tempfile::tempdir().unwrap();
         let path = dir.path().join("config.json");
 
+        save(&path, &BankrConfig::default()).unwrap();
         save(&path, &BankrConfig::default()).unwrap();
 
-        let mode = fs::metadata(&path).unwrap().permissions().mode() & 0o777;
-        assert_eq!(mode, 0o600);
+        for file in ["config.json", "config.json.bak"] {
+            let mode = fs::metadata(dir.path().join(file)).unwrap().permissions().mode() & 0o777;
+            assert_eq!(mode, 0o600, "{file}");
+        }
     }
 
     #[test]
     fn camel_case_json_keys() {
-        let config =
-            BankrConfig { api_key: Some("key".to_owned()), api_url: Some("url".to_owned()) };
+        let config = BankrConfig {
+            default: Profile {
+                api_key: Some("key".to_owned()),
+                api_url: Some("url".to_owned()),
+                llm_key: Some("llm".to_owned()),
+                ..Profile::default()
+            },
+            default_profile: Some("bot".to_owned()),
+            ..BankrConfig::default()
+        };
         let json = serde_json::to_string(&config).unwrap();
         assert!(json.contains("apiKey"));
         assert!(json.contains("apiUrl"));
+        assert!(json.contains("llmKey"));
+        assert!(json.contains("defaultProfile"));
         assert!(!json.contains("api_key"));
         assert!(!json.contains("api_url"));
     }
@@ -179,23 +826,119 @@ mod tests {
 
     #[test]
     fn resolve_api_key_priority() {
-        let config = BankrConfig { api_key: Some("from_config".to_owned()), api_url: None };
+        let config = profile("from_config");
 
         // CLI flag wins over everything.
         assert_eq!(
-            resolve_api_key(Some("from_flag"), Some("from_env"), &config),
-            Some("from_flag".to_owned()),
+            resolve_api_key(Some("from_flag"), Some("from_env"), None, &config).unwrap(),
+            key("from_flag"),
         );
 
         // Env var wins over config.
-        assert_eq!(resolve_api_key(None, Some("from_env"), &config), Some("from_env".to_owned()),);
+        assert_eq!(
+            resolve_api_key(None, Some("from_env"), None, &config).unwrap(),
+            key("from_env"),
+        );
 
         // Config is the fallback.
-        assert_eq!(resolve_api_key(None, None, &config), Some("from_config".to_owned()),);
+        assert_eq!(resolve_api_key(None, None, None, &config).unwrap(), key("from_config"));
 
         // None if nothing is set.
-        let empty = BankrConfig::default();
-        assert_eq!(resolve_api_key(None, None, &empty), None);
+        let empty = Profile::default();
+        assert_eq!(resolve_api_key(None, None, None, &empty).unwrap(), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn api_key_command_is_run_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("runs");
+        let command = format!("echo run >> {}; printf 'bk_from_cmd\\nmetadata\\n'", log.display());
+        let mut config = profile("from_config");
+        config.api_key_command = Some(command);
+
+        for _ in 0..2 {
+            assert_eq!(resolve_api_key(None, None, None, &config).unwrap(), key("bk_from_cmd"));
+        }
+        assert_eq!(fs::read_to_string(&log).unwrap().lines().count(), 1);
+
+        // The flag overrides the profile's command; explicit keys override both.
+        assert_eq!(
+            resolve_api_key(None, None, Some("echo bk_flag_cmd"), &config).unwrap(),
+            key("bk_flag_cmd")
+        );
+        assert_eq!(
+            resolve_api_key(None, Some("from_env"), Some("exit 1"), &config).unwrap(),
+            key("from_env")
+        );
+
+        assert!(resolve_api_key(None, None, Some("exit 3"), &config).is_err());
+        assert!(resolve_api_key(None, None, Some("true"), &config).is_err());
+    }
+
+    #[test]
+    fn resolve_llm_key_falls_back_to_api_key() {
+        let mut config = profile("api_config");
+        let llm = |flag, api, config: &Profile| resolve_llm_key(flag, api, None, config).unwrap();
+
+        assert_eq!(llm(None, Some("api_flag"), &config), key("api_flag"));
+        assert_eq!(llm(None, None, &config), key("api_config"));
+
+        config.llm_key = Some("llm_config".to_owned());
+        assert_eq!(llm(None, Some("api_flag"), &config), key("llm_config"));
+        assert_eq!(llm(Some("llm_flag"), Some("api_flag"), &config), key("llm_flag"));
+    }
+
+    #[test]
+    fn active_profile_resolution() {
+        let json = r#"{"apiKey":"bk_default","profiles":{"bot":{"apiKey":"bk_bot","apiUrl":"http://bot"}}}"#;
+        let mut config: BankrConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.profile_names().collect::<Vec<_>>(), ["default", "bot"]);
+
+        let (name, active) = config.active_profile(None).unwrap();
+        assert_eq!((name, active.api_key.as_deref()), ("default", Some("bk_default")));
+
+        config.default_profile = Some("bot".to_owned());
+        let (name, active) = config.active_profile(None).unwrap();
+        assert_eq!((name, active.api_url.as_deref()), ("bot", Some("http://bot")));
+
+        // An explicit selection wins over `defaultProfile`.
+        assert_eq!(config.active_profile(Some("default")).unwrap().0, "default");
+        assert!(config.active_profile(Some("missing")).is_err());
+    }
+
+    #[test]
+    fn config_keys_are_validated() {
+        let mut profile = Profile::default();
+        for (key, value, stored) in [
+            ("apiUrl", "https://api.example.com/", "https://api.example.com"),
+            ("llm-key", "bk_llm_1234567890", "bk_llm_1234567890"),
+            ("defaultChain", "Base", "base"),
+            ("POLL_INTERVAL", "5", "5"),
+            ("outputFormat", "RAW", "raw"),
+        ] {
+            let key: ConfigKey = key.parse().unwrap();
+            key.set(&mut profile, value).unwrap();
+            assert_eq!(key.get(&profile).as_deref(), Some(stored));
+        }
+        assert_eq!(profile.default_chain, Some(8453));
+        assert_eq!(ConfigKey::LlmKey.display(&profile).as_deref(), Some("bk_llm...7890"));
+
+        for (key, value) in [
+            (ConfigKey::ApiKey, "bk with space"),
+            (ConfigKey::ApiUrl, "api.bankr.bot"),
+            (ConfigKey::ApiKeyCommand, " \t "),
+            (ConfigKey::DefaultChain, "solana"),
+            (ConfigKey::PollInterval, "-1"),
+            (ConfigKey::MaxPollAttempts, "0"),
+            (ConfigKey::OutputFormat, "yaml"),
+        ] {
+            assert!(key.set(&mut profile, value).is_err(), "{key} = {value}");
+        }
+        assert!("colour".parse::<ConfigKey>().unwrap_err().contains("valid keys: apiKey"));
+
+        ConfigKey::DefaultChain.unset(&mut profile);
+        assert_eq!(profile.default_chain, None);
     }
 
     #[test]
@@ -204,7 +947,7 @@ mod tests {
         let path = dir.path().join("empty.json");
         fs::write(&path, "").unwrap();
 
-        let config = load(&path);
+        let config = load(&path).unwrap();
         assert_eq!(config, BankrConfig::default());
     }
 
@@ -19,6 +19,11 @@ pub(crate) fn error(msg: &str) {
     println!("\x1b[31m\u{2716} {msg}\x1b[0m");
 }
 
+/// Print a warning in yellow to stderr: `⚠ {msg}`.
+pub(crate) fn warning(msg: &str) {
+    eprintln!("\x1b[33m\u{26a0} {msg}\x1b[0m");
+}
+
 /// Pretty-print a `JobResponse` for the `status` command.
 pub(crate) fn print_job_status(job: &JobResponse) {
     println!("Job ID:      {}", job.job_id);
@@ -46,6 +51,16 @@ pub(crate) fn print_job_status(job: &JobResponse) {
                 println!("Response:");
                 println!("{resp}");
             }
+            if let Some(txs) = job.transactions.as_deref().filter(|txs| !txs.is_empty()) {
+                println!();
+                println!("Transactions:");
+                for tx in txs {
+                    match tx.metadata().and_then(|m| m.summary()) {
+                        Some(summary) => println!("  - {}: {summary}", tx.kind()),
+                        None => println!("  - {}", tx.kind()),
+                    }
+                }
+            }
         }
         JobStatus::Failed => {
             if let Some(err) = &job.error {
@@ -60,6 +75,7 @@ pub(crate) fn print_job_status(job: &JobResponse) {
 /// Pretty-print the `whoami` response with config metadata.
 pub(crate) fn print_whoami(
     config_path: &Path,
+    profile: &str,
     api_key: &str,
     api_url: &str,
     resp: &UserInfoResponse,
@@ -71,6 +87,7 @@ pub(crate) fn print_whoami(
     println!("Bankr API URL:  {api_url}");
     println!("Source:  {}", config_path.display());
     println!("Config:  {}", config_path.display());
+    println!("Profile:  {profile}");
     println!();
 
     print_wallets(resp);
@@ -0,0 +1,260 @@
+//! Passphrase-encrypted API keys.
+//!
+//! `bankr-cli login --encrypt` stores the key as `encryptedApiKey` instead of
+//! `apiKey`: the passphrase is stretched with scrypt into a 256-bit key, which
+//! seals the API key with XChaCha20-Poly1305. The passphrase is read from
+//! `BANKR_PASSPHRASE` (for CI) or prompted for on the terminal.
+//!
+//! The passphrase, the derived key and the decrypted API key are wiped from
+//! memory when dropped. The HTTP client built from the key keeps its own
+//! copy for the rest of the process.
+
+use std::{
+    collections::BTreeMap,
+    io::IsTerminal,
+    sync::{Mutex, PoisonError},
+};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use chacha20poly1305::{
+    KeyInit, XChaCha20Poly1305, XNonce,
+    aead::{Aead, AeadCore, OsRng, rand_core::RngCore},
+};
+use eyre::{Result, eyre};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+/// Environment variable holding the passphrase for non-interactive use.
+pub const PASSPHRASE_ENV: &str = "BANKR_PASSPHRASE";
+
+/// Key derivation function used for an [`EncryptedKey`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Kdf {
+    /// scrypt with the cost parameters stored alongside.
+    Scrypt,
+}
+
+/// An API key sealed with a passphrase, as stored in the config file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptedKey {
+    /// Key derivation function.
+    pub kdf: Kdf,
+    /// scrypt CPU/memory cost, as log2(N).
+    pub log_n: u8,
+    /// scrypt block size.
+    pub r: u32,
+    /// scrypt parallelism.
+    pub p: u32,
+    /// Base64 KDF salt.
+    pub salt: String,
+    /// Base64 XChaCha20-Poly1305 nonce.
+    pub nonce: String,
+    /// Base64 ciphertext with its authentication tag.
+    pub ciphertext: String,
+}
+
+impl EncryptedKey {
+    /// scrypt cost for new keys: N = 2^15, r = 8, p = 1 (32 MiB).
+    const LOG_N: u8 = 15;
+    const R: u32 = 8;
+    const P: u32 = 1;
+
+    /// Largest accepted scrypt cost, so a tampered config file cannot make
+    /// unlocking take gigabytes of memory: N = 2^20, r = 32 (4 GiB at most).
+    const MAX_LOG_N: u8 = 20;
+    const MAX_R: u32 = 32;
+    const MAX_P: u32 = 16;
+
+    /// Encrypt `key` under `passphrase` with a fresh salt and nonce.
+    ///
+    /// # Errors
+    ///
+    /// Fails only if encryption itself fails, which does not happen for
+    /// keys of any realistic length.
+    pub fn seal(key: &str, passphrase: &str) -> Result<Self> {
+        Self::seal_with_cost(key, passphrase, Self::LOG_N)
+    }
+
+    fn seal_with_cost(key: &str, passphrase: &str, log_n: u8) -> Result<Self> {
+        let mut salt = [0u8; 16];
+        OsRng.fill_bytes(&mut salt);
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+        let mut sealed = Self {
+            kdf: Kdf::Scrypt,
+            log_n,
+            r: Self::R,
+            p: Self::P,
+            salt: STANDARD.encode(salt),
+            nonce: STANDARD.encode(nonce),
+            ciphertext: String::new(),
+        };
+        let ciphertext = sealed
+            .cipher(passphrase)?
+            .encrypt(&nonce, key.as_bytes())
+            .map_err(|_| eyre!("failed to encrypt the API key"))?;
+        sealed.ciphertext = STANDARD.encode(ciphertext);
+        Ok(sealed)
+    }
+
+    /// Decrypt the key with `passphrase`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the passphrase is wrong or the stored value is malformed.
+    pub fn open(&self, passphrase: &str) -> Result<Zeroizing<String>> {
+        let malformed = |field: &str| eyre!("invalid encryptedApiKey: bad {field}");
+        let nonce = STANDARD.decode(&self.nonce).map_err(|_| malformed("nonce"))?;
+        if nonce.len() != 24 {
+            return Err(malformed("nonce"));
+        }
+        let ciphertext = STANDARD.decode(&self.ciphertext).map_err(|_| malformed("ciphertext"))?;
+
+        let plaintext = Zeroizing::new(
+            self.cipher(passphrase)?
+                .decrypt(XNonce::from_slice(&nonce), ciphertext.as_slice())
+                .map_err(|_| eyre!("wrong passphrase for the encrypted API key"))?,
+        );
+        std::str::from_utf8(&plaintext)
+            .map(|key| Zeroizing::new(key.to_owned()))
+            .map_err(|_| malformed("ciphertext"))
+    }
+
+    /// The cipher keyed by scrypt(`passphrase`, salt).
+    fn cipher(&self, passphrase: &str) -> Result<XChaCha20Poly1305> {
+        let salt =
+            STANDARD.decode(&self.salt).map_err(|_| eyre!("invalid encryptedApiKey: bad salt"))?;
+        if self.log_n > Self::MAX_LOG_N || self.r > Self::MAX_R || self.p > Self::MAX_P {
+            return Err(eyre!(
+                "invalid encryptedApiKey scrypt parameters: logN, r and p must be at most {}, {} and {}",
+                Self::MAX_LOG_N,
+                Self::MAX_R,
+                Self::MAX_P
+            ));
+        }
+        let params = scrypt::Params::new(self.log_n, self.r, self.p, 32)
+            .map_err(|e| eyre!("invalid encryptedApiKey scrypt parameters: {e}"))?;
+        let mut key = Zeroizing::new([0u8; 32]);
+        scrypt::scrypt(passphrase.as_bytes(), &salt, &params, key.as_mut_slice())
+            .map_err(|e| eyre!("scrypt failed: {e}"))?;
+        Ok(XChaCha20Poly1305::new(key.as_slice().into()))
+    }
+}
+
+/// Decrypt `sealed`, asking for the passphrase (see [`passphrase`]) unless
+/// the same key was already unlocked by this process.
+///
+/// # Errors
+///
+/// Fails if no passphrase is available or it is wrong.
+pub fn unlock(sealed: &EncryptedKey) -> Result<Zeroizing<String>> {
+    static CACHE: Mutex<BTreeMap<String, Zeroizing<String>>> = Mutex::new(BTreeMap::new());
+
+    let mut cache = CACHE.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(key) = cache.get(&sealed.ciphertext) {
+        return Ok(key.clone());
+    }
+    let key = sealed.open(&passphrase("Passphrase for the Bankr API key: ")?)?;
+    cache.insert(sealed.ciphertext.clone(), key.clone());
+    Ok(key)
+}
+
+/// The passphrase from `BANKR_PASSPHRASE`, else prompted for without echo.
+///
+/// # Errors
+///
+/// Fails if the variable is unset and there is no terminal to prompt on.
+pub fn passphrase(prompt: &str) -> Result<Zeroizing<String>> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(Zeroizing::new(passphrase));
+    }
+    if !std::io::stdin().is_terminal() {
+        return Err(eyre!(
+            "the API key is encrypted: set {PASSPHRASE_ENV} or run in a terminal to enter the passphrase"
+        ));
+    }
+    rpassword::prompt_password(prompt)
+        .map(Zeroizing::new)
+        .map_err(|e| eyre!("failed to read passphrase: {e}"))
+}
+
+/// Ask for a new passphrase, twice on a terminal; `BANKR_PASSPHRASE` is used
+/// as is.
+///
+/// # Errors
+///
+/// Fails if the passphrase is empty or the two entries differ.
+pub fn new_passphrase() -> Result<Zeroizing<String>> {
+    let from_env = std::env::var_os(PASSPHRASE_ENV).is_some();
+    let passphrase = passphrase("New passphrase: ")?;
+    if passphrase.is_empty() {
+        return Err(eyre!("passphrase cannot be empty"));
+    }
+    if !from_env {
+        let confirm = rpassword::prompt_password("Repeat passphrase: ")
+            .map(Zeroizing::new)
+            .map_err(|e| eyre!("failed to read passphrase: {e}"))?;
+        if confirm != passphrase {
+            return Err(eyre!("passphrases do not match"));
+        }
+    }
+    Ok(passphrase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Cheap scrypt cost so the tests stay fast in debug builds.
+    const TEST_LOG_N: u8 = 4;
+
+    #[test]
+    fn seal_then_open_round_trips() {
+        let a = EncryptedKey::seal_with_cost("bk_TESTKEY123456", "hunter2", TEST_LOG_N).unwrap();
+        let b = EncryptedKey::seal_with_cost("bk_TESTKEY123456", "hunter2", TEST_LOG_N).unwrap();
+        assert_eq!(*a.open("hunter2").unwrap(), "bk_TESTKEY123456");
+        assert_eq!(*b.open("hunter2").unwrap(), "bk_TESTKEY123456");
+        assert!(!a.ciphertext.contains("TESTKEY"));
+        // Fresh salt and nonce every time.
+        assert_ne!(a.salt, b.salt);
+        assert_ne!(a.nonce, b.nonce);
+    }
+
+    #[test]
+    fn wrong_passphrase_or_tampering_is_rejected() {
+        let mut sealed =
+            EncryptedKey::seal_with_cost("bk_TESTKEY123456", "pw", TEST_LOG_N).unwrap();
+        let err = sealed.open("hunter3").unwrap_err();
+        assert!(err.to_string().contains("wrong passphrase"), "{err}");
+
+        sealed.nonce = STANDARD.encode([0u8; 24]);
+        assert!(sealed.open("pw").is_err());
+        sealed.nonce = "not base64!".to_owned();
+        assert!(sealed.open("pw").unwrap_err().to_string().contains("bad nonce"));
+    }
+
+    #[test]
+    fn excessive_scrypt_cost_is_rejected() {
+        let sealed = EncryptedKey::seal_with_cost("bk_TESTKEY123456", "pw", TEST_LOG_N).unwrap();
+        for tampered in [
+            EncryptedKey { log_n: 21, ..sealed.clone() },
+            EncryptedKey { r: 33, ..sealed.clone() },
+            EncryptedKey { p: 17, ..sealed },
+        ] {
+            let err = tampered.open("pw").unwrap_err();
+            assert!(err.to_string().contains("scrypt parameters"), "{err}");
+        }
+    }
+
+    #[test]
+    fn serializes_camel_case() {
+        let sealed = EncryptedKey::seal_with_cost("bk_TESTKEY123456", "pw", TEST_LOG_N).unwrap();
+        let json = serde_json::to_value(&sealed).unwrap();
+        assert_eq!(json["kdf"], "scrypt");
+        assert_eq!(json["logN"], TEST_LOG_N);
+        let back: EncryptedKey = serde_json::from_value(json).unwrap();
+        assert_eq!(back, sealed);
+    }
+}
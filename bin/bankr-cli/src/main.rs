@@ -8,7 +8,8 @@
 //! |----------|----------------------------------------------|
 //! | login    | Authenticate with the Bankr API              |
 //! | logout   | Clear stored credentials                     |
-//! | config   | Manage CLI configuration                     |
+//! | config   | Show or change CLI configuration             |
+//! | profile  | Manage named configuration profiles          |
 //! | whoami   | Show current authentication info             |
 //! | prompt   | Send a prompt to the Bankr AI agent          |
 //! | status   | Check the status of a job                    |
@@ -16,17 +17,21 @@
 //! | sign     | Sign messages, typed data, or transactions   |
 //! | skills   | Show all Bankr AI agent skills with examples |
 //! | submit   | Submit a transaction to the blockchain       |
+//! | llm      | Chat with models via the Bankr LLM Gateway   |
 //!
 //! ## Usage
 //!
 //! ```text
 //! bankr-cli login
 //! bankr-cli whoami
+//! bankr-cli --profile trading whoami
 //! bankr-cli prompt "what is the price of ETH?"
 //! bankr-cli status <job_id>
 //! bankr-cli cancel <job_id>
 //! bankr-cli sign personal "Hello, Bankr!"
-//! bankr-cli submit --chain-id 8453 --to 0x... --value "1000000000000000000"
+//! bankr-cli sign verify personal "Hello, Bankr!" --signature 0x...
+//! bankr-cli submit --chain base --to 0x... --value 0.1ether
+//! bankr-cli llm chat "explain EIP-1559 in one sentence"
 //! ```
 
 // CLI binary — allow print macros and expect/unwrap for user-facing output.
@@ -35,12 +40,14 @@
 mod commands;
 pub mod config;
 pub mod display;
+pub mod keystore;
 
 use std::path::PathBuf;
 
 use bankr_agent_api::BankrAgentClient;
 use clap::{Parser, Subcommand};
-use commands::sign::SignCommands;
+use commands::{config_cmd::ConfigCommands, profile::ProfileCommands, sign::SignCommands};
+use config::OutputFormat;
 use eyre::{Result, WrapErr, eyre};
 
 /// Bankr AI agent CLI.
@@ -51,6 +58,11 @@ struct Cli {
     #[arg(long, env = "BANKR_API_KEY", global = true, hide_env_values = true)]
     api_key: Option<String>,
 
+    /// Shell command that prints the API key (overrides the config's
+    /// `apiKeyCommand` and `apiKey`).
+    #[arg(long, env = "BANKR_API_KEY_COMMAND", global = true)]
+    api_key_command: Option<String>,
+
     /// Base URL override (default: https://api.bankr.bot).
     #[arg(long, env = "BANKR_BASE_URL", global = true)]
     base_url: Option<String>,
@@ -59,6 +71,11 @@ struct Cli {
     #[arg(long, global = true)]
     config: Option<PathBuf>,
 
+    /// Configuration profile to use (default: the config's `defaultProfile`,
+    /// else `default`).
+    #[arg(long, env = "BANKR_PROFILE", global = true)]
+    profile: Option<String>,
+
     /// Output raw JSON instead of pretty-printed.
     #[arg(long, global = true, default_value_t = false)]
     raw: bool,
@@ -74,13 +91,32 @@ enum Commands {
         /// API key to store. If omitted you will be prompted interactively.
         #[arg(long)]
         api_key: Option<String>,
+
+        /// Separate LLM Gateway key to store.
+        #[arg(long)]
+        llm_key: Option<String>,
+
+        /// Store the API key encrypted with a passphrase (read from
+        /// BANKR_PASSPHRASE or prompted for) instead of in plain text. The
+        /// LLM Gateway key cannot be encrypted, so this excludes --llm-key.
+        #[arg(long, default_value_t = false, conflicts_with = "llm_key")]
+        encrypt: bool,
     },
 
     /// Clear stored credentials.
     Logout,
 
-    /// Manage CLI configuration.
-    Config,
+    /// Show or change CLI configuration.
+    Config {
+        #[command(subcommand)]
+        command: Option<ConfigCommands>,
+    },
+
+    /// Manage named configuration profiles.
+    Profile {
+        #[command(subcommand)]
+        command: ProfileCommands,
+    },
 
     /// Show current authentication info.
     Whoami,
@@ -98,13 +134,14 @@ enum Commands {
         #[arg(long, default_value_t = true)]
         wait: bool,
 
-        /// Polling interval in seconds.
-        #[arg(long, default_value_t = 2)]
-        poll_interval: u64,
+        /// Polling interval in seconds (default: `pollInterval`, else 2).
+        #[arg(long)]
+        poll_interval: Option<u64>,
 
-        /// Maximum number of poll attempts.
-        #[arg(long, default_value_t = 60)]
-        max_attempts: u32,
+        /// Maximum number of poll attempts (default: `maxPollAttempts`, else
+        /// 60).
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+        max_attempts: Option<u32>,
     },
 
     /// Check the status of a job.
@@ -135,11 +172,13 @@ enum Commands {
         #[arg(long)]
         to: String,
 
-        /// Chain ID (e.g. 8453 for Base, 1 for Ethereum).
-        #[arg(long)]
-        chain_id: u64,
+        /// Chain name or ID (e.g. `base` or 8453, `eth` or 1; default:
+        /// `defaultChain`).
+        #[arg(long = "chain", visible_alias = "chain-id", value_parser = commands::parse_chain)]
+        chain_id: Option<u64>,
 
-        /// Value in wei.
+        /// Value, e.g. `0.1ether`, `0.1eth` (the chain's native token),
+        /// `25gwei` or a bare integer in wei.
         #[arg(long)]
         value: Option<String>,
 
@@ -151,11 +190,11 @@ enum Commands {
         #[arg(long)]
         gas: Option<String>,
 
-        /// EIP-1559 max fee per gas.
+        /// EIP-1559 max fee per gas, e.g. `2gwei` or wei.
         #[arg(long)]
         max_fee_per_gas: Option<String>,
 
-        /// EIP-1559 priority fee.
+        /// EIP-1559 priority fee, e.g. `0.1gwei` or wei.
         #[arg(long)]
         max_priority_fee_per_gas: Option<String>,
 
@@ -171,6 +210,9 @@ enum Commands {
         #[arg(long, default_value_t = false)]
         no_wait: bool,
     },
+
+    /// Chat with models via the Bankr LLM Gateway.
+    Llm(commands::llm::LlmArgs),
 }
 
 #[tokio::main]
@@ -195,58 +237,112 @@ async fn main() -> Result<()> {
     // Commands that do NOT require an API key
     // -----------------------------------------------------------------
     match &cli.command {
-        Commands::Login { api_key } => {
+        Commands::Login { api_key, llm_key, encrypt } => {
             return commands::auth::cmd_login(
                 api_key.as_deref(),
+                llm_key.as_deref(),
+                *encrypt,
                 cli.base_url.as_deref(),
                 &config_path,
+                cli.profile.as_deref(),
             )
             .await;
         }
         Commands::Logout => {
-            return commands::auth::cmd_logout(&config_path);
+            return commands::auth::cmd_logout(&config_path, cli.profile.as_deref());
+        }
+        Commands::Config { command: None } => {
+            return commands::config_cmd::cmd_config(&config_path, cli.profile.as_deref());
+        }
+        Commands::Config { command: Some(command) } => {
+            return commands::config_cmd::cmd_config_key(
+                &config_path,
+                command,
+                cli.profile.as_deref(),
+                cli.raw,
+            );
+        }
+        Commands::Profile { command } => {
+            return commands::profile::cmd_profile(
+                &config_path,
+                command,
+                cli.profile.as_deref(),
+                cli.raw,
+            );
+        }
+        Commands::Sign { kind: SignCommands::Verify(args) } if !args.check_wallet => {
+            return commands::sign::cmd_verify(None, args, cli.raw).await;
         }
-        Commands::Config => {
-            return commands::config_cmd::cmd_config(&config_path);
+        Commands::Llm(args) => {
+            // The LLM Gateway has its own key, falling back to the API key.
+            let cfg = config::load_or_default(&config_path);
+            let (_, profile) = cfg.active_profile(cli.profile.as_deref())?;
+            let raw = cli.raw || profile.output_format == Some(OutputFormat::Raw);
+            return commands::llm::cmd_llm(
+                args,
+                cli.api_key.as_deref(),
+                cli.api_key_command.as_deref(),
+                profile,
+                raw,
+            )
+            .await;
         }
         _ => {} // fall through to API-key-requiring commands
     }
 
     // -----------------------------------------------------------------
-    // Resolve API key: flag > env > config
+    // Resolve API key: flag > env > key command > active profile
     // -----------------------------------------------------------------
-    let cfg = config::load(&config_path);
-    let api_key = config::resolve_api_key(cli.api_key.as_deref(), None, &cfg).ok_or_else(|| {
+    let cfg = config::load_or_default(&config_path);
+    let (profile_name, profile) = cfg.active_profile(cli.profile.as_deref())?;
+    let api_key = config::resolve_api_key(
+        cli.api_key.as_deref(),
+        None,
+        cli.api_key_command.as_deref(),
+        profile,
+    )?
+    .ok_or_else(|| {
         eyre!(
-            "API key required. Set via --api-key, BANKR_API_KEY env var, or run `bankr-cli login`."
+            "API key required for profile {profile_name}. Set via --api-key, BANKR_API_KEY env var, --api-key-command, or run `bankr-cli login`."
         )
     })?;
 
     let base_url =
-        cli.base_url.as_deref().or(cfg.api_url.as_deref()).unwrap_or("https://api.bankr.bot");
+        cli.base_url.as_deref().or(profile.api_url.as_deref()).unwrap_or("https://api.bankr.bot");
 
     let client = BankrAgentClient::with_base_url(&api_key, base_url).map_err(|e| eyre!("{e}"))?;
+    let raw = cli.raw || profile.output_format == Some(OutputFormat::Raw);
 
     match cli.command {
         Commands::Whoami => {
-            commands::whoami::cmd_whoami(&client, cli.raw, &config_path, &api_key, base_url).await
+            commands::whoami::cmd_whoami(
+                &client,
+                raw,
+                &config_path,
+                profile_name,
+                &api_key,
+                base_url,
+            )
+            .await
         }
-        Commands::Skills => commands::skills::cmd_skills(&client, cli.raw).await,
+        Commands::Skills => commands::skills::cmd_skills(&client, raw).await,
         Commands::Prompt { prompt, thread_id, wait, poll_interval, max_attempts } => {
             commands::prompt::cmd_prompt(
                 &client,
                 &prompt,
                 thread_id.as_deref(),
                 wait,
-                poll_interval,
-                max_attempts,
-                cli.raw,
+                poll_interval.or(profile.poll_interval).unwrap_or(2),
+                max_attempts.or(profile.max_poll_attempts).unwrap_or(60),
+                raw,
             )
             .await
         }
-        Commands::Status { job_id } => commands::job::cmd_job(&client, &job_id, cli.raw).await,
-        Commands::Cancel { job_id } => commands::job::cmd_cancel(&client, &job_id, cli.raw).await,
-        Commands::Sign { kind } => commands::sign::cmd_sign(&client, kind, cli.raw).await,
+        Commands::Status { job_id } => commands::job::cmd_job(&client, &job_id, raw).await,
+        Commands::Cancel { job_id } => commands::job::cmd_cancel(&client, &job_id, raw).await,
+        Commands::Sign { kind } => {
+            commands::sign::cmd_sign(&client, kind, profile.default_chain, raw).await
+        }
         Commands::Submit {
             to,
             chain_id,
@@ -262,7 +358,7 @@ async fn main() -> Result<()> {
             commands::submit::cmd_submit(
                 &client,
                 &to,
-                chain_id,
+                commands::resolve_chain(chain_id, profile.default_chain)?,
                 value,
                 data,
                 gas,
@@ -271,12 +367,16 @@ async fn main() -> Result<()> {
                 nonce,
                 description,
                 no_wait,
-                cli.raw,
+                raw,
             )
             .await
         }
-        // Login / Logout / Config already handled above.
-        Commands::Login { .. } | Commands::Logout | Commands::Config => unreachable!(),
+        // Login / Logout / Config / Profile / Llm already handled above.
+        Commands::Login { .. } |
+        Commands::Logout |
+        Commands::Config { .. } |
+        Commands::Profile { .. } |
+        Commands::Llm(_) => unreachable!(),
     }
 }
 
@@ -0,0 +1,513 @@
+//! End-to-end tests of the `bankr-cli` binary against the mock server.
+
+#![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+
+use std::process::Output;
+
+use bankr_agent_api_mock::{DEFAULT_EVM_ADDRESS, Failure, JobSchedule, MockConfig, MockServer};
+use serde_json::Value;
+use tokio::process::Command;
+
+async fn server() -> MockServer {
+    MockServer::start_with(MockConfig {
+        schedule: JobSchedule::immediate(),
+        ..MockConfig::default()
+    })
+    .await
+    .unwrap()
+}
+
+/// Run the CLI against `server` with an isolated config file.
+async fn run(server: &MockServer, args: &[&str]) -> Output {
+    let dir = tempfile::tempdir().unwrap();
+    Command::new(env!("CARGO_BIN_EXE_bankr-cli"))
+        .args(["--api-key", "bk_test", "--base-url", &server.url(), "--raw", "--config"])
+        .arg(dir.path().join("config.json"))
+        .args(args)
+        .env_remove("BANKR_API_KEY")
+        .env_remove("BANKR_BASE_URL")
+        .env_remove("BANKR_LLM_KEY")
+        .env_remove("BANKR_LLM_URL")
+        .output()
+        .await
+        .unwrap()
+}
+
+fn json(output: &Output) -> Value {
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[tokio::test]
+async fn prompt_waits_for_completion() {
+    let server = server().await;
+    let out = json(&run(&server, &["prompt", "price of ETH", "--poll-interval", "0"]).await);
+
+    assert_eq!(out["status"], "completed");
+    assert_eq!(out["response"], "Mock response to: price of ETH");
+    assert_eq!(server.requests()[0].api_key.as_deref(), Some("bk_test"));
+}
+
+#[tokio::test]
+async fn status_and_cancel() {
+    let server = MockServer::start_with(MockConfig {
+        schedule: JobSchedule {
+            processing_after: std::time::Duration::from_hours(1),
+            ..JobSchedule::immediate()
+        },
+        ..MockConfig::default()
+    })
+    .await
+    .unwrap();
+    let client =
+        bankr_agent_api::BankrAgentClient::with_base_url("bk_test", &server.url()).unwrap();
+    let created = client
+        .submit_prompt(&bankr_agent_api::types::PromptRequest {
+            prompt: "swap".to_owned(),
+            thread_id: None,
+        })
+        .await
+        .unwrap();
+
+    let out = json(&run(&server, &["status", &created.job_id]).await);
+    assert_eq!(out["status"], "pending");
+
+    let out = json(&run(&server, &["cancel", &created.job_id]).await);
+    assert_eq!(out["status"], "cancelled");
+}
+
+#[tokio::test]
+async fn sign_and_submit() {
+    let server = server().await;
+
+    let out = json(&run(&server, &["sign", "personal", "hello"]).await);
+    assert_eq!(out["signer"], DEFAULT_EVM_ADDRESS);
+
+    let out = json(
+        &run(
+            &server,
+            &["submit", "--to", "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913", "--chain-id", "8453"],
+        )
+        .await,
+    );
+    assert_eq!(out["status"], "success");
+    assert_eq!(out["chainId"], 8453);
+    let hash = out["transactionHash"].as_str().unwrap();
+    assert_eq!(out["explorerUrl"], format!("https://basescan.org/tx/{hash}"));
+
+    let output = run(
+        &server,
+        &["submit", "--to", "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913", "--chain", "eth"],
+    )
+    .await;
+    assert_eq!(json(&output)["chainId"], 1);
+
+    let output = run(
+        &server,
+        &[
+            "submit",
+            "--to",
+            "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913",
+            "--chain",
+            "base",
+            "--value",
+            "0.1ether",
+            "--max-fee-per-gas",
+            "2 gwei",
+        ],
+    )
+    .await;
+    assert!(output.status.success());
+
+    let output = run(
+        &server,
+        &[
+            "submit",
+            "--to",
+            "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913",
+            "--chain",
+            "base",
+            "--value",
+            "0.1",
+        ],
+    )
+    .await;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("needs a unit"));
+
+    // POL is Polygon's native token; on Base it must not be read as ETH.
+    let before = server.requests().len();
+    let output = run(
+        &server,
+        &[
+            "submit",
+            "--to",
+            "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913",
+            "--chain",
+            "base",
+            "--value",
+            "1pol",
+        ],
+    )
+    .await;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("not the native token of Base"));
+    assert_eq!(server.requests().len(), before);
+
+    let output = run(
+        &server,
+        &["submit", "--to", "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913", "--chain", "10"],
+    )
+    .await;
+    assert_eq!(json(&output)["chainId"], 10);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("not supported"));
+
+    let output = run(
+        &server,
+        &["submit", "--to", "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913", "--chain", "solana"],
+    )
+    .await;
+    assert!(!output.status.success());
+}
+
+#[tokio::test]
+async fn errors_exit_non_zero() {
+    let server = server().await;
+    server.inject(Failure::ReadOnly);
+
+    let out = run(&server, &["sign", "personal", "hello"]).await;
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("Read-only API key"));
+
+    server.inject(Failure::RevertedSubmit);
+    let out = json(
+        &run(
+            &server,
+            &["submit", "--to", "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913", "--chain-id", "8453"],
+        )
+        .await,
+    );
+    assert_eq!(out["status"], "reverted");
+
+    let out = run(&server, &["prompt", "gm", "--max-attempts", "0"]).await;
+    assert!(!out.status.success());
+    assert!(server.requests().iter().all(|r| r.path != "/agent/prompt"));
+}
+
+#[tokio::test]
+async fn sign_verify_works_offline() {
+    let signature = "0xb91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd\
+                     6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c";
+    let verify = |expect: &'static str| {
+        Command::new(env!("CARGO_BIN_EXE_bankr-cli"))
+            .args(["--raw", "sign", "verify", "personal", "Some data", "--signature", signature])
+            .args(["--expect", expect])
+            .env_remove("BANKR_API_KEY")
+            .env("HOME", std::env::temp_dir())
+            .output()
+    };
+
+    let out = json(&verify("0x2c7536e3605d9c16a7a3d7b1898e529396a65c23").await.unwrap());
+    assert_eq!(out["signer"], "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23");
+
+    let out = verify("0x0000000000000000000000000000000000000001").await.unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("does not match"));
+}
+
+#[tokio::test]
+async fn sign_verify_decodes_transaction() {
+    // The signed transaction from the EIP-155 specification.
+    let raw = "0xf86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a7640000\
+               8025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f76\
+               1aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+    let output = Command::new(env!("CARGO_BIN_EXE_bankr-cli"))
+        .args(["--raw", "sign", "verify", "transaction", raw])
+        .env_remove("BANKR_API_KEY")
+        .env("HOME", std::env::temp_dir())
+        .output()
+        .await
+        .unwrap();
+
+    let out = json(&output);
+    assert_eq!(out["signer"], "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F");
+    assert_eq!(out["transaction"]["chainId"], 1);
+    assert_eq!(out["transaction"]["nonce"], 9);
+    assert_eq!(out["transaction"]["value"], "1000000000000000000");
+    let hash = out["hash"].as_str().unwrap();
+    assert_eq!(out["explorerUrl"], format!("https://etherscan.io/tx/{hash}"));
+}
+
+#[tokio::test]
+async fn llm_chat_and_models() {
+    let server = server().await;
+    let url = server.url();
+
+    let out = run(&server, &["llm", "chat", "gm", "--llm-url", &url]).await;
+    assert!(out.status.success(), "stderr: {}", String::from_utf8_lossy(&out.stderr));
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "Echo: gm\n");
+    assert!(String::from_utf8_lossy(&out.stderr).contains("tokens: 1 prompt + 2 completion = 3"));
+    // Without an LLM key the API key is used.
+    assert_eq!(server.requests()[0].api_key.as_deref(), Some("bk_test"));
+
+    let args = ["llm", "--llm-key", "bk_llm", "--llm-url", &url, "chat", "gm", "--no-stream"];
+    let out = json(&run(&server, &args).await);
+    assert_eq!(out["choices"][0]["message"]["content"], "Echo: gm");
+    assert_eq!(server.requests()[1].api_key.as_deref(), Some("bk_llm"));
+
+    let out = json(&run(&server, &["llm", "models", "--llm-url", &url]).await);
+    assert!(out.as_array().is_some_and(|models| !models.is_empty()));
+}
+
+#[tokio::test]
+async fn logout_keeps_unknown_keys_and_refuses_malformed_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.json");
+    let logout = || {
+        Command::new(env!("CARGO_BIN_EXE_bankr-cli"))
+            .args(["logout", "--config"])
+            .arg(&path)
+            .env_remove("BANKR_API_KEY")
+            .output()
+    };
+
+    std::fs::write(&path, r#"{"apiKey":"bk_test","llmKey":"bk_llm","theme":"dark"}"#).unwrap();
+    assert!(logout().await.unwrap().status.success());
+    let saved: Value = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+    assert_eq!(saved, serde_json::json!({ "llmKey": "bk_llm", "theme": "dark" }));
+    let backup = std::fs::read_to_string(dir.path().join("config.json.bak")).unwrap();
+    assert!(!backup.contains("bk_test"));
+    assert!(backup.contains("dark"));
+
+    std::fs::write(&path, "{ not json").unwrap();
+    let out = logout().await.unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("invalid config file"));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "{ not json");
+
+    // Commands that only read the config warn and fall back to the defaults.
+    let server = server().await;
+    let out = Command::new(env!("CARGO_BIN_EXE_bankr-cli"))
+        .args(["--api-key", "bk_test", "--base-url", &server.url(), "--raw", "--config"])
+        .arg(&path)
+        .arg("whoami")
+        .output()
+        .await
+        .unwrap();
+    assert!(String::from_utf8_lossy(&out.stderr).contains("invalid config file"));
+    assert_eq!(json(&out)["success"], true);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "{ not json");
+}
+
+#[tokio::test]
+async fn profiles_select_key_and_url() {
+    let server = server().await;
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.json");
+    std::fs::write(&path, r#"{"apiKey":"bk_personal","apiUrl":"http://127.0.0.1:9"}"#).unwrap();
+    let cli = |args: &[&str], profile: Option<&str>| {
+        let mut cmd = Command::new(env!("CARGO_BIN_EXE_bankr-cli"));
+        cmd.args(["--config", path.to_str().unwrap()])
+            .args(args)
+            .env_remove("BANKR_API_KEY")
+            .env_remove("BANKR_BASE_URL")
+            .env_remove("BANKR_PROFILE");
+        if let Some(profile) = profile {
+            cmd.env("BANKR_PROFILE", profile);
+        }
+        cmd.output()
+    };
+
+    let url = server.url();
+    let out =
+        cli(&["profile", "add", "bot", "--key", "bk_bot_key", "--url", &url], None).await.unwrap();
+    assert!(out.status.success(), "stderr: {}", String::from_utf8_lossy(&out.stderr));
+    assert!(!cli(&["profile", "add", "bot"], None).await.unwrap().status.success());
+    let out = cli(&["login", "--llm-key", "bk_llm"], Some("my bot")).await.unwrap();
+    assert!(String::from_utf8_lossy(&out.stderr).contains("invalid profile name"));
+
+    // BANKR_PROFILE selects the profile for a single command.
+    let out = json(&cli(&["--raw", "whoami"], Some("bot")).await.unwrap());
+    assert!(out["success"].as_bool().unwrap());
+    assert_eq!(server.requests()[0].api_key.as_deref(), Some("bk_bot_key"));
+
+    assert!(cli(&["profile", "use", "bot"], None).await.unwrap().status.success());
+    let out = cli(&["whoami"], None).await.unwrap();
+    assert!(String::from_utf8_lossy(&out.stdout).contains("Profile:  bot"));
+    let out = cli(&["config"], Some("default")).await.unwrap();
+    assert!(String::from_utf8_lossy(&out.stdout).contains("Profile:      default"));
+
+    let out = json(&cli(&["--raw", "profile", "list"], None).await.unwrap());
+    assert_eq!(out[0]["name"], "default");
+    assert_eq!(out[1]["name"], "bot");
+    assert_eq!(out[1]["active"], true);
+    assert_eq!(out[1]["apiKey"], "bk_bot..._key");
+
+    assert!(cli(&["profile", "remove", "bot"], None).await.unwrap().status.success());
+    let saved: Value = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+    assert_eq!(
+        saved,
+        serde_json::json!({ "apiKey": "bk_personal", "apiUrl": "http://127.0.0.1:9" })
+    );
+
+    let out = cli(&["whoami"], Some("bot")).await.unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("profile `bot` not found"));
+}
+
+#[tokio::test]
+async fn config_get_set_unset() {
+    let server = server().await;
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.json");
+    let cli = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_bankr-cli"))
+            .args(["--config", path.to_str().unwrap()])
+            .args(args)
+            .env_remove("BANKR_API_KEY")
+            .env_remove("BANKR_BASE_URL")
+            .env_remove("BANKR_PROFILE")
+            .output()
+    };
+    let stdout = |out: &Output| {
+        assert!(out.status.success(), "stderr: {}", String::from_utf8_lossy(&out.stderr));
+        String::from_utf8_lossy(&out.stdout).trim().to_owned()
+    };
+
+    let url = server.url();
+    for (key, value) in [
+        ("apiKey", "bk_config_test"),
+        ("api-url", url.as_str()),
+        ("defaultChain", "polygon"),
+        ("outputFormat", "raw"),
+    ] {
+        stdout(&cli(&["config", "set", key, value]).await.unwrap());
+    }
+    assert_eq!(stdout(&cli(&["config", "get", "apiKey"]).await.unwrap()), "bk_con...test");
+    assert_eq!(
+        stdout(&cli(&["config", "get", "apiKey", "--reveal"]).await.unwrap()),
+        "bk_config_test"
+    );
+    assert_eq!(stdout(&cli(&["config", "get", "defaultChain"]).await.unwrap()), "polygon");
+    assert_eq!(stdout(&cli(&["config", "path"]).await.unwrap()), path.to_str().unwrap());
+
+    let out = cli(&["config", "set", "pollInterval", "soon"]).await.unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("invalid value `soon` for pollInterval"));
+    assert!(!cli(&["config", "set", "colour", "blue"]).await.unwrap().status.success());
+
+    // `submit` falls back to `defaultChain`; `outputFormat` makes output compact.
+    let to = "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913";
+    let out = cli(&["submit", "--to", to]).await.unwrap();
+    let text = stdout(&out);
+    assert!(!text.contains('\n'));
+    assert_eq!(serde_json::from_str::<Value>(&text).unwrap()["chainId"], 137);
+    assert_eq!(server.requests()[0].api_key.as_deref(), Some("bk_config_test"));
+
+    stdout(&cli(&["config", "unset", "defaultChain"]).await.unwrap());
+    let out = cli(&["submit", "--to", to]).await.unwrap();
+    assert!(String::from_utf8_lossy(&out.stderr).contains("--chain is required"));
+
+    let out = json(&cli(&["--raw", "config", "list"]).await.unwrap());
+    assert_eq!(out["apiKey"], "bk_con...test");
+    assert_eq!(out["outputFormat"], "raw");
+    assert!(out.get("defaultChain").is_none());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn api_key_command_supplies_the_key() {
+    let server = server().await;
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.json");
+    std::fs::write(&path, r#"{"apiKey":"bk_stored","apiKeyCommand":"echo bk_from_helper"}"#)
+        .unwrap();
+    let whoami = |extra: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_bankr-cli"))
+            .args(["--raw", "--base-url", &server.url(), "--config", path.to_str().unwrap()])
+            .args(extra)
+            .arg("whoami")
+            .env_remove("BANKR_API_KEY")
+            .env_remove("BANKR_API_KEY_COMMAND")
+            .env_remove("BANKR_PROFILE")
+            .output()
+    };
+
+    json(&whoami(&[]).await.unwrap());
+    json(&whoami(&["--api-key-command", "printf 'bk_flag\\nignored'"]).await.unwrap());
+    let out = whoami(&["--api-key-command", "exit 7"]).await.unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("apiKeyCommand `exit 7` failed"));
+
+    let keys: Vec<_> = server.requests().into_iter().map(|r| r.api_key.unwrap()).collect();
+    assert_eq!(keys, ["bk_from_helper", "bk_flag"]);
+}
+
+#[tokio::test]
+async fn login_encrypt_stores_sealed_key() {
+    let server = server().await;
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.json");
+    let cli = |args: &[&str], passphrase: Option<&str>| {
+        let mut cmd = Command::new(env!("CARGO_BIN_EXE_bankr-cli"));
+        cmd.args(["--raw", "--base-url", &server.url(), "--config", path.to_str().unwrap()])
+            .args(args)
+            .env_remove("BANKR_API_KEY")
+            .env_remove("BANKR_API_KEY_COMMAND")
+            .env_remove("BANKR_PROFILE")
+            .env_remove("BANKR_PASSPHRASE");
+        if let Some(passphrase) = passphrase {
+            cmd.env("BANKR_PASSPHRASE", passphrase);
+        }
+        cmd.output()
+    };
+
+    let out = cli(&["login", "--api-key", "bk_sealed_key", "--encrypt"], Some("hunter2")).await;
+    assert!(out.unwrap().status.success());
+    let saved = std::fs::read_to_string(&path).unwrap();
+    assert!(!saved.contains("bk_sealed_key"), "{saved}");
+    let saved: Value = serde_json::from_str(&saved).unwrap();
+    assert!(saved.get("apiKey").is_none());
+    assert_eq!(saved["encryptedApiKey"]["kdf"], "scrypt");
+
+    json(&cli(&["whoami"], Some("hunter2")).await.unwrap());
+    let out = cli(&["whoami"], Some("wrong")).await.unwrap();
+    assert!(String::from_utf8_lossy(&out.stderr).contains("wrong passphrase"));
+    let out = cli(&["whoami"], None).await.unwrap();
+    assert!(String::from_utf8_lossy(&out.stderr).contains("set BANKR_PASSPHRASE"));
+
+    let keys: Vec<_> = server.requests().into_iter().map(|r| r.api_key.unwrap()).collect();
+    assert_eq!(keys, ["bk_sealed_key", "bk_sealed_key"]);
+
+    // The LLM key would be stored in plain text, so the combination is refused.
+    let args = ["login", "--api-key", "bk_sealed_key", "--llm-key", "bk_llm", "--encrypt"];
+    let out = cli(&args, Some("hunter2")).await.unwrap();
+    assert!(!out.status.success());
+    assert!(!std::fs::read_to_string(&path).unwrap().contains("bk_llm"));
+}
+
+#[tokio::test]
+async fn login_encrypt_leaves_no_plain_key_behind() {
+    let server = server().await;
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.json");
+    std::fs::write(&path, r#"{"apiKey":"bk_plain"}"#).unwrap();
+
+    let out = Command::new(env!("CARGO_BIN_EXE_bankr-cli"))
+        .args(["--raw", "--base-url", &server.url(), "--config", path.to_str().unwrap()])
+        .args(["login", "--api-key", "bk_plain", "--encrypt"])
+        .env_remove("BANKR_API_KEY")
+        .env_remove("BANKR_API_KEY_COMMAND")
+        .env_remove("BANKR_PROFILE")
+        .env("BANKR_PASSPHRASE", "hunter2")
+        .output()
+        .await
+        .unwrap();
+    assert!(out.status.success());
+
+    for entry in std::fs::read_dir(dir.path()).unwrap() {
+        let file = entry.unwrap().path();
+        let contents = std::fs::read_to_string(&file).unwrap();
+        assert!(!contents.contains("bk_plain"), "{}: {contents}", file.display());
+    }
+}
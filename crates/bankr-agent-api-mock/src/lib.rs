@@ -0,0 +1,237 @@
+//! # bankr-agent-api-mock
+//!
+//! A local HTTP server implementing the [Bankr Agent API](https://docs.bankr.bot/agent-api/overview)
+//! and the OpenAI-compatible [LLM Gateway](https://docs.bankr.bot/llm-gateway/overview)
+//! (`/v1/chat/completions`, `/v1/models`) for offline integration tests of
+//! `bankr-agent-api` and `bankr-cli`.
+//!
+//! Jobs move `pending` → `processing` → `completed` on a configurable
+//! [`JobSchedule`], and [`Failure`]s can be injected to exercise error paths
+//! (401, 403 read-only, 429 with `resetAt`, reverted submits, …).
+//!
+//! ```rust,no_run
+//! use bankr_agent_api_mock::{Failure, MockServer};
+//!
+//! # async fn example() -> std::io::Result<()> {
+//! let server = MockServer::start().await?;
+//! server.inject(Failure::ReadOnly);
+//!
+//! // Point a client (or `bankr-cli --base-url`) at `server.url()`.
+//! println!("listening on {}", server.url());
+//! # Ok(())
+//! # }
+//! ```
+
+mod routes;
+mod state;
+
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::Duration,
+};
+
+use hyper::{server::conn::http1, service::service_fn};
+use hyper_util::rt::TokioIo;
+use tokio::{net::TcpListener, task::JoinHandle};
+use tracing::debug;
+
+use crate::state::State;
+
+/// EVM address reported by `/agent/me` and used as signer by default.
+pub const DEFAULT_EVM_ADDRESS: &str = "0x1234567890AbcdEF1234567890aBcdef12345678";
+
+/// When jobs change status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JobSchedule {
+    /// Time from creation until the job moves from `pending` to `processing`.
+    pub processing_after: Duration,
+    /// Time spent in `processing` before the job moves to `completed` (or
+    /// `failed`).
+    pub completed_after: Duration,
+}
+
+impl JobSchedule {
+    /// Jobs complete immediately on the first poll.
+    pub const fn immediate() -> Self {
+        Self { processing_after: Duration::ZERO, completed_after: Duration::ZERO }
+    }
+}
+
+impl Default for JobSchedule {
+    fn default() -> Self {
+        Self {
+            processing_after: Duration::from_millis(50),
+            completed_after: Duration::from_millis(150),
+        }
+    }
+}
+
+/// Server configuration.
+#[derive(Debug, Clone)]
+pub struct MockConfig {
+    /// Only accept this API key. When `None`, any non-empty key is accepted.
+    pub api_key: Option<String>,
+    /// Job status schedule.
+    pub schedule: JobSchedule,
+    /// EVM wallet address of the mock account.
+    pub evm_address: String,
+}
+
+impl Default for MockConfig {
+    fn default() -> Self {
+        Self {
+            api_key: None,
+            schedule: JobSchedule::default(),
+            evm_address: DEFAULT_EVM_ADDRESS.to_owned(),
+        }
+    }
+}
+
+/// A failure to inject into the next matching request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    /// 401 Authentication required (any endpoint).
+    Unauthorized,
+    /// 403 Agent API access not enabled (any endpoint).
+    AgentAccessDisabled,
+    /// 403 IP address not allowed (any endpoint).
+    IpNotAllowed,
+    /// 403 Read-only API key (`/agent/sign` and `/agent/submit`).
+    ReadOnly,
+    /// 429 Daily limit exceeded (`/agent/prompt` and `/v1/chat/completions`).
+    RateLimited {
+        /// Reset time reported in `resetAt` (Unix ms).
+        reset_at: u64,
+    },
+    /// 500 Internal server error (any endpoint).
+    ServerError,
+    /// The submitted transaction is mined but reverts (`/agent/submit`).
+    RevertedSubmit,
+    /// The created job ends in `failed` with this message (`/agent/prompt`).
+    JobFailed(String),
+}
+
+impl Failure {
+    /// Whether this failure applies to the given endpoint.
+    fn applies_to(&self, endpoint: routes::Endpoint) -> bool {
+        use routes::Endpoint;
+
+        match self {
+            Self::Unauthorized |
+            Self::AgentAccessDisabled |
+            Self::IpNotAllowed |
+            Self::ServerError => true,
+            Self::ReadOnly => matches!(endpoint, Endpoint::Sign | Endpoint::Submit),
+            Self::RateLimited { .. } => {
+                matches!(endpoint, Endpoint::Prompt | Endpoint::ChatCompletions)
+            }
+            Self::JobFailed(_) => endpoint == Endpoint::Prompt,
+            Self::RevertedSubmit => endpoint == Endpoint::Submit,
+        }
+    }
+}
+
+/// A request received by the server.
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    /// HTTP method.
+    pub method: String,
+    /// Request path.
+    pub path: String,
+    /// Value of the `X-API-Key` header, or else the `Authorization: Bearer`
+    /// token, if present.
+    pub api_key: Option<String>,
+    /// Parsed JSON body (`Null` if empty or not JSON).
+    pub body: serde_json::Value,
+}
+
+pub(crate) type Shared = Arc<Mutex<State>>;
+
+pub(crate) fn lock(state: &Shared) -> MutexGuard<'_, State> {
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// A running mock server. The server stops when this value is dropped.
+#[derive(Debug)]
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Shared,
+    task: JoinHandle<()>,
+}
+
+impl MockServer {
+    /// Start a server with the default configuration on a random local port.
+    pub async fn start() -> std::io::Result<Self> {
+        Self::start_with(MockConfig::default()).await
+    }
+
+    /// Start a server with `config` on a random local port.
+    pub async fn start_with(config: MockConfig) -> std::io::Result<Self> {
+        Self::bind(SocketAddr::from(([127, 0, 0, 1], 0)), config).await
+    }
+
+    /// Start a server with `config` on `addr`.
+    pub async fn bind(addr: SocketAddr, config: MockConfig) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(addr).await?;
+        let addr = listener.local_addr()?;
+        let state: Shared = Arc::new(Mutex::new(State::new(config)));
+
+        let shared = Arc::clone(&state);
+        let task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let state = Arc::clone(&shared);
+                tokio::spawn(async move {
+                    let svc = service_fn(move |req| {
+                        let state = Arc::clone(&state);
+                        async move { Ok::<_, Infallible>(routes::handle(&state, req).await) }
+                    });
+                    if let Err(e) =
+                        http1::Builder::new().serve_connection(TokioIo::new(stream), svc).await
+                    {
+                        debug!(error = %e, "Connection error");
+                    }
+                });
+            }
+        });
+
+        Ok(Self { addr, state, task })
+    }
+
+    /// The socket address the server listens on.
+    pub const fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Base URL to pass to the client, e.g. `http://127.0.0.1:54321`.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Inject `failure` into the next request it applies to.
+    pub fn inject(&self, failure: Failure) {
+        self.inject_times(failure, 1);
+    }
+
+    /// Inject `failure` into the next `times` requests it applies to.
+    pub fn inject_times(&self, failure: Failure, times: usize) {
+        lock(&self.state).failures.extend(std::iter::repeat_n(failure, times));
+    }
+
+    /// All requests received so far, in order.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        lock(&self.state).requests.clone()
+    }
+
+    /// Wait until the server task exits (it only exits on accept errors).
+    pub async fn serve_forever(mut self) {
+        let _ = (&mut self.task).await;
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
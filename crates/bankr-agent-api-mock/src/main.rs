@@ -0,0 +1,27 @@
+//! Standalone mock Bankr Agent API server.
+//!
+//! ```text
+//! bankr-agent-api-mock [ADDR]    # default 127.0.0.1:8787
+//! bankr-cli --base-url http://127.0.0.1:8787 --api-key bk_test whoami
+//! ```
+
+#![allow(clippy::print_stdout, clippy::print_stderr)]
+
+use std::net::SocketAddr;
+
+use bankr_agent_api_mock::{MockConfig, MockServer};
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let addr: SocketAddr = match std::env::args().nth(1) {
+        Some(arg) => arg.parse().map_err(|e| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("{arg}: {e}"))
+        })?,
+        None => SocketAddr::from(([127, 0, 0, 1], 8787)),
+    };
+
+    let server = MockServer::bind(addr, MockConfig::default()).await?;
+    println!("Mock Bankr Agent API listening on {}", server.url());
+    server.serve_forever().await;
+    Ok(())
+}
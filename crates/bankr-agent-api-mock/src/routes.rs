@@ -0,0 +1,425 @@
+//! Request routing and endpoint handlers.
+
+use http_body_util::{BodyExt, Full};
+use hyper::{
+    Method, Request, Response, StatusCode,
+    body::{Bytes, Incoming},
+    header::{CONTENT_TYPE, HeaderValue},
+};
+use serde_json::{Value, json};
+
+use crate::{Failure, RecordedRequest, Shared, lock, state::Status};
+
+/// The endpoints served by the mock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Endpoint {
+    Me,
+    Prompt,
+    Job,
+    Cancel,
+    Sign,
+    Submit,
+    ChatCompletions,
+    Models,
+}
+
+type Reply = (StatusCode, Value);
+
+fn error(status: StatusCode, error: &str, message: &str) -> Reply {
+    (status, json!({ "error": error, "message": message }))
+}
+
+/// Handle a single HTTP request.
+pub(crate) async fn handle(state: &Shared, req: Request<Incoming>) -> Response<Full<Bytes>> {
+    let (parts, body) = req.into_parts();
+    let bytes = body.collect().await.map(|b| b.to_bytes()).unwrap_or_default();
+    let body: Value = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    let header = |name| parts.headers.get(name).and_then(|v: &HeaderValue| v.to_str().ok());
+    let api_key = header("x-api-key")
+        .or_else(|| header("authorization").and_then(|v| v.strip_prefix("Bearer ")))
+        .map(str::to_owned);
+    let path = parts.uri.path().to_owned();
+
+    let mut state = lock(state);
+    state.requests.push(RecordedRequest {
+        method: parts.method.to_string(),
+        path: path.clone(),
+        api_key: api_key.clone(),
+        body: body.clone(),
+    });
+
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let route = match (&parts.method, segments.as_slice()) {
+        (&Method::GET, ["agent", "me"]) => Some((Endpoint::Me, None)),
+        (&Method::POST, ["agent", "prompt"]) => Some((Endpoint::Prompt, None)),
+        (&Method::GET, ["agent", "job", id]) => Some((Endpoint::Job, Some(*id))),
+        (&Method::POST, ["agent", "job", id, "cancel"]) => Some((Endpoint::Cancel, Some(*id))),
+        (&Method::POST, ["agent", "sign"]) => Some((Endpoint::Sign, None)),
+        (&Method::POST, ["agent", "submit"]) => Some((Endpoint::Submit, None)),
+        (&Method::POST, ["v1", "chat", "completions"]) => Some((Endpoint::ChatCompletions, None)),
+        (&Method::GET, ["v1", "models"]) => Some((Endpoint::Models, None)),
+        _ => None,
+    };
+    let endpoint = route.map(|(endpoint, _)| endpoint);
+
+    let (status, value) = match route {
+        None => error(StatusCode::NOT_FOUND, "Not found", &format!("No route for {path}")),
+        Some((endpoint, id)) => {
+            let authorized = match (&state.config.api_key, &api_key) {
+                (_, None) => false,
+                (Some(expected), Some(got)) => expected == got,
+                (None, Some(got)) => !got.is_empty(),
+            };
+            if authorized {
+                let failure = state.take_failure(endpoint);
+                dispatch(&mut state, endpoint, id.unwrap_or_default(), &body, failure)
+            } else {
+                error(
+                    StatusCode::UNAUTHORIZED,
+                    "Authentication required",
+                    "Please provide a valid API key",
+                )
+            }
+        }
+    };
+    drop(state);
+
+    let stream = status == StatusCode::OK &&
+        endpoint == Some(Endpoint::ChatCompletions) &&
+        body.get("stream").and_then(Value::as_bool) == Some(true);
+    let (content_type, text) = if stream {
+        ("text/event-stream", event_stream(&value))
+    } else {
+        ("application/json", value.to_string())
+    };
+    let mut resp = Response::new(Full::new(Bytes::from(text)));
+    *resp.status_mut() = status;
+    resp.headers_mut().insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+    resp
+}
+
+fn dispatch(
+    state: &mut crate::state::State,
+    endpoint: Endpoint,
+    id: &str,
+    body: &Value,
+    failure: Option<Failure>,
+) -> Reply {
+    match failure {
+        Some(Failure::Unauthorized) => {
+            return error(
+                StatusCode::UNAUTHORIZED,
+                "Authentication required",
+                "Please provide a valid API key",
+            );
+        }
+        Some(Failure::AgentAccessDisabled) => {
+            return error(
+                StatusCode::FORBIDDEN,
+                "Agent API access not enabled",
+                "Enable agent access for your API key at bankr.bot/api",
+            );
+        }
+        Some(Failure::IpNotAllowed) => {
+            return error(
+                StatusCode::FORBIDDEN,
+                "IP address not allowed",
+                "IP address not allowed for this API key",
+            );
+        }
+        Some(Failure::ReadOnly) => {
+            return error(
+                StatusCode::FORBIDDEN,
+                "Read-only API key",
+                "This API key has read-only access. Update your API key permissions at bankr.bot/api",
+            );
+        }
+        Some(Failure::RateLimited { reset_at }) => {
+            return (
+                StatusCode::TOO_MANY_REQUESTS,
+                json!({
+                    "error": "Daily limit exceeded",
+                    "message": "You have reached your daily API limit",
+                    "resetAt": reset_at,
+                    "limit": 100,
+                    "used": 100,
+                }),
+            );
+        }
+        Some(Failure::ServerError) => {
+            return error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Internal server error",
+                "Injected failure",
+            );
+        }
+        Some(Failure::RevertedSubmit | Failure::JobFailed(_)) | None => {}
+    }
+
+    match endpoint {
+        Endpoint::Me => me(state),
+        Endpoint::Prompt => prompt(state, body, failure),
+        Endpoint::Job => {
+            state.job(id).map_or_else(|| job_not_found(id), |job| (StatusCode::OK, job))
+        }
+        Endpoint::Cancel => match state.cancel_job(id) {
+            None => job_not_found(id),
+            Some(Ok(job)) => (StatusCode::OK, job),
+            Some(Err(status)) => {
+                let (error, message) = if status == Status::Failed {
+                    ("Job already failed", "Cannot cancel a failed job")
+                } else {
+                    ("Job already completed", "Cannot cancel a completed job")
+                };
+                (
+                    StatusCode::BAD_REQUEST,
+                    json!({ "success": false, "error": error, "message": message }),
+                )
+            }
+        },
+        Endpoint::Sign => sign(state, body),
+        Endpoint::Submit => submit(state, body, failure == Some(Failure::RevertedSubmit)),
+        Endpoint::ChatCompletions => chat_completion(state, body),
+        Endpoint::Models => models(),
+    }
+}
+
+fn job_not_found(id: &str) -> Reply {
+    error(StatusCode::NOT_FOUND, "Job not found", &format!("No job found with ID {id}"))
+}
+
+fn me(state: &crate::state::State) -> Reply {
+    (
+        StatusCode::OK,
+        json!({
+            "success": true,
+            "wallets": [
+                { "chain": "evm", "address": state.config.evm_address },
+                { "chain": "solana", "address": "9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin" },
+            ],
+            "socialAccounts": [{ "platform": "farcaster", "username": "mock" }],
+            "refCode": "MOCK0000-BNKR",
+            "bankrClub": { "active": false, "subscriptionType": null, "renewOrCancelOn": null },
+            "leaderboard": { "score": 0, "rank": null },
+        }),
+    )
+}
+
+fn prompt(state: &mut crate::state::State, body: &Value, failure: Option<Failure>) -> Reply {
+    let Some(prompt) = body.get("prompt").and_then(Value::as_str) else {
+        return error(StatusCode::BAD_REQUEST, "Invalid request", "prompt is required");
+    };
+    if prompt.chars().count() > 10_000 {
+        return error(
+            StatusCode::BAD_REQUEST,
+            "Invalid request",
+            "Prompt exceeds 10,000 characters",
+        );
+    }
+    let thread_id = body.get("threadId").and_then(Value::as_str).map(str::to_owned);
+    let failure = match failure {
+        Some(Failure::JobFailed(message)) => Some(message),
+        _ => None,
+    };
+    (StatusCode::ACCEPTED, state.create_job(prompt.to_owned(), thread_id, failure))
+}
+
+/// Validate the transaction object shared by sign and submit.
+fn transaction(body: &Value) -> Result<&Value, Reply> {
+    let Some(tx) = body.get("transaction").filter(|tx| tx.is_object()) else {
+        return Err(error(
+            StatusCode::BAD_REQUEST,
+            "Invalid request",
+            "transaction object is required",
+        ));
+    };
+    if tx.get("to").and_then(Value::as_str).is_none() {
+        return Err(error(StatusCode::BAD_REQUEST, "Invalid request", "transaction.to is required"));
+    }
+    if tx.get("chainId").and_then(Value::as_u64).is_none() {
+        return Err(error(
+            StatusCode::BAD_REQUEST,
+            "Invalid request",
+            "transaction.chainId is required",
+        ));
+    }
+    Ok(tx)
+}
+
+/// A deterministic, well-formed hex string of `len` bytes.
+fn fake_hex(seed: u64, len: usize) -> String {
+    let mut out = String::with_capacity(2 + len * 2);
+    out.push_str("0x");
+    let mut x = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+    for _ in 0..len {
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        out.push_str(&format!("{:02x}", x & 0xff));
+    }
+    out
+}
+
+fn sign(state: &mut crate::state::State, body: &Value) -> Reply {
+    let signature_type = body.get("signatureType").and_then(Value::as_str).unwrap_or_default();
+    let missing = match signature_type {
+        "personal_sign" => {
+            body.get("message").and_then(Value::as_str).is_none().then_some("message")
+        }
+        "eth_signTypedData_v4" => {
+            body.get("typedData").is_none_or(Value::is_null).then_some("typedData")
+        }
+        "eth_signTransaction" => {
+            if let Err(reply) = transaction(body) {
+                return reply;
+            }
+            None
+        }
+        _ => {
+            return error(
+                StatusCode::BAD_REQUEST,
+                "Invalid request",
+                "signatureType must be one of personal_sign, eth_signTypedData_v4, eth_signTransaction",
+            );
+        }
+    };
+    if let Some(field) = missing {
+        return error(
+            StatusCode::BAD_REQUEST,
+            "Invalid request",
+            &format!("{field} is required for {signature_type}"),
+        );
+    }
+
+    let n = state.next_id();
+    (
+        StatusCode::OK,
+        json!({
+            "success": true,
+            "signature": fake_hex(n, 65),
+            "signer": state.config.evm_address,
+            "signatureType": signature_type,
+        }),
+    )
+}
+
+fn submit(state: &mut crate::state::State, body: &Value, reverted: bool) -> Reply {
+    let chain_id = match transaction(body) {
+        Ok(tx) => tx.get("chainId").cloned().unwrap_or(Value::Null),
+        Err(reply) => return reply,
+    };
+    let n = state.next_id();
+    let wait = body.get("waitForConfirmation").and_then(Value::as_bool).unwrap_or(true);
+
+    let mut resp = json!({
+        "success": true,
+        "transactionHash": fake_hex(n, 32),
+        "status": if !wait { "pending" } else if reverted { "reverted" } else { "success" },
+        "signer": state.config.evm_address,
+        "chainId": chain_id,
+    });
+    if wait && let Some(obj) = resp.as_object_mut() {
+        obj.insert("blockNumber".to_owned(), json!((12_345_000 + n).to_string()));
+        obj.insert("gasUsed".to_owned(), json!("21000"));
+    }
+    (StatusCode::OK, resp)
+}
+
+fn models() -> Reply {
+    let model =
+        |id: &str, owned_by: &str| json!({ "id": id, "object": "model", "owned_by": owned_by });
+    (
+        StatusCode::OK,
+        json!({
+            "object": "list",
+            "data": [
+                model("claude-sonnet-4.5", "anthropic"),
+                model("gemini-2.5-flash", "google"),
+                model("gpt-5-mini", "openai"),
+            ],
+        }),
+    )
+}
+
+/// Reply to the last user message with `Echo: <message>`.
+fn chat_completion(state: &mut crate::state::State, body: &Value) -> Reply {
+    let Some(model) = body.get("model").and_then(Value::as_str) else {
+        return error(StatusCode::BAD_REQUEST, "Invalid request", "model is required");
+    };
+    let messages = body.get("messages").and_then(Value::as_array).map(Vec::as_slice);
+    let Some(messages @ [_, ..]) = messages else {
+        return error(StatusCode::BAD_REQUEST, "Invalid request", "messages must not be empty");
+    };
+    let content =
+        |m: &Value| m.get("content").and_then(Value::as_str).unwrap_or_default().to_owned();
+    let last_user = messages
+        .iter()
+        .rev()
+        .find(|m| m.get("role").and_then(Value::as_str) == Some("user"))
+        .map(content)
+        .unwrap_or_default();
+    let reply = format!("Echo: {last_user}");
+    let words = |s: &str| s.split_whitespace().count();
+    let prompt_tokens: usize = messages.iter().map(|m| words(&content(m))).sum();
+    let completion_tokens = words(&reply);
+
+    let n = state.next_id();
+    (
+        StatusCode::OK,
+        json!({
+            "id": format!("chatcmpl-mock-{n}"),
+            "object": "chat.completion",
+            "created": 1_700_000_000 + n,
+            "model": model,
+            "choices": [{
+                "index": 0,
+                "message": { "role": "assistant", "content": reply },
+                "finish_reason": "stop",
+            }],
+            "usage": {
+                "prompt_tokens": prompt_tokens,
+                "completion_tokens": completion_tokens,
+                "total_tokens": prompt_tokens + completion_tokens,
+            },
+        }),
+    )
+}
+
+/// Re-encode a chat completion as server-sent events: one chunk per word,
+/// a usage-only chunk, then `[DONE]`.
+fn event_stream(completion: &Value) -> String {
+    let chunk = |choices: Value, usage: Value| {
+        json!({
+            "id": completion["id"],
+            "object": "chat.completion.chunk",
+            "created": completion["created"],
+            "model": completion["model"],
+            "choices": choices,
+            "usage": usage,
+        })
+    };
+    let text = completion["choices"][0]["message"]["content"].as_str().unwrap_or_default();
+
+    let mut events: Vec<Value> = text
+        .split_inclusive(' ')
+        .enumerate()
+        .map(|(i, word)| {
+            let delta = if i == 0 {
+                json!({ "role": "assistant", "content": word })
+            } else {
+                json!({ "content": word })
+            };
+            chunk(json!([{ "index": 0, "delta": delta, "finish_reason": null }]), Value::Null)
+        })
+        .collect();
+    events.push(chunk(json!([{ "index": 0, "delta": {}, "finish_reason": "stop" }]), Value::Null));
+    events.push(chunk(json!([]), completion["usage"].clone()));
+
+    let mut out = String::new();
+    for data in events.iter().map(Value::to_string).chain(["[DONE]".to_owned()]) {
+        out.push_str("data: ");
+        out.push_str(&data);
+        out.push_str("\n\n");
+    }
+    out
+}
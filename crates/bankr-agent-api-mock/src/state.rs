@@ -0,0 +1,282 @@
+//! In-memory server state: accounts, jobs and injected failures.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use serde_json::{Value, json};
+
+use crate::{Failure, MockConfig, RecordedRequest, routes::Endpoint};
+
+/// Job status at a point in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Status {
+    Pending,
+    Processing,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl Status {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Processing => "processing",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::Cancelled => "cancelled",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Job {
+    id: String,
+    thread_id: String,
+    prompt: String,
+    created: Instant,
+    created_at: SystemTime,
+    cancelled_at: Option<SystemTime>,
+    failure: Option<String>,
+}
+
+#[derive(Debug)]
+pub(crate) struct State {
+    pub(crate) config: MockConfig,
+    pub(crate) failures: VecDeque<Failure>,
+    pub(crate) requests: Vec<RecordedRequest>,
+    jobs: HashMap<String, Job>,
+    next_id: u64,
+}
+
+impl State {
+    pub(crate) fn new(config: MockConfig) -> Self {
+        Self {
+            config,
+            failures: VecDeque::new(),
+            requests: Vec::new(),
+            jobs: HashMap::new(),
+            next_id: 1,
+        }
+    }
+
+    /// Remove and return the first queued failure that applies to `endpoint`.
+    pub(crate) fn take_failure(&mut self, endpoint: Endpoint) -> Option<Failure> {
+        let pos = self.failures.iter().position(|f| f.applies_to(endpoint))?;
+        self.failures.remove(pos)
+    }
+
+    /// Allocate a new sequential identifier.
+    pub(crate) const fn next_id(&mut self) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    /// Create a job and return its `202 Accepted` body.
+    pub(crate) fn create_job(
+        &mut self,
+        prompt: String,
+        thread_id: Option<String>,
+        failure: Option<String>,
+    ) -> Value {
+        let n = self.next_id();
+        let id = format!("job_{n:08}");
+        let thread_id = thread_id.unwrap_or_else(|| format!("thr_{n:08}"));
+        let job = Job {
+            id: id.clone(),
+            thread_id: thread_id.clone(),
+            prompt,
+            created: Instant::now(),
+            created_at: SystemTime::now(),
+            cancelled_at: None,
+            failure,
+        };
+        self.jobs.insert(id.clone(), job);
+
+        json!({
+            "success": true,
+            "jobId": id,
+            "threadId": thread_id,
+            "status": "pending",
+            "message": "Job submitted successfully",
+        })
+    }
+
+    fn status(&self, job: &Job) -> Status {
+        if job.cancelled_at.is_some() {
+            return Status::Cancelled;
+        }
+        let elapsed = job.created.elapsed();
+        let schedule = self.config.schedule;
+        if elapsed < schedule.processing_after {
+            Status::Pending
+        } else if elapsed < schedule.processing_after + schedule.completed_after {
+            Status::Processing
+        } else if job.failure.is_some() {
+            Status::Failed
+        } else {
+            Status::Completed
+        }
+    }
+
+    /// The `GET /agent/job/{id}` body, or `None` if the job does not exist.
+    pub(crate) fn job(&self, id: &str) -> Option<Value> {
+        let job = self.jobs.get(id)?;
+        let status = self.status(job);
+        let schedule = self.config.schedule;
+        let started_at = job.created_at + schedule.processing_after;
+        let completed_at = started_at + schedule.completed_after;
+
+        let mut body = json!({
+            "success": true,
+            "jobId": job.id,
+            "threadId": job.thread_id,
+            "status": status.as_str(),
+            "prompt": job.prompt,
+            "createdAt": iso8601(job.created_at),
+            "cancellable": matches!(status, Status::Pending | Status::Processing),
+        });
+        let mut set = |key: &str, value: Value| {
+            if let Some(obj) = body.as_object_mut() {
+                obj.insert(key.to_owned(), value);
+            }
+        };
+
+        if matches!(status, Status::Processing | Status::Completed | Status::Failed) {
+            set("startedAt", json!(iso8601(started_at)));
+            let mut updates =
+                vec![json!({ "message": "Processing prompt", "timestamp": iso8601(started_at) })];
+            if status != Status::Processing {
+                updates.push(
+                    json!({ "message": "Generating response", "timestamp": iso8601(completed_at) }),
+                );
+            }
+            set("statusUpdates", Value::Array(updates));
+        }
+
+        match status {
+            Status::Completed => {
+                set("response", json!(format!("Mock response to: {}", job.prompt)));
+                set("richData", json!([]));
+                set("completedAt", json!(iso8601(completed_at)));
+                set("processingTime", json!(millis(schedule.completed_after)));
+            }
+            Status::Failed => {
+                set("error", json!(job.failure));
+                set("completedAt", json!(iso8601(completed_at)));
+            }
+            Status::Cancelled => {
+                set("cancelledAt", json!(job.cancelled_at.map(iso8601)));
+            }
+            Status::Pending | Status::Processing => {}
+        }
+
+        Some(body)
+    }
+
+    /// Cancel a job. Returns the new job body, or the terminal status that
+    /// prevented cancellation.
+    pub(crate) fn cancel_job(&mut self, id: &str) -> Option<Result<Value, Status>> {
+        let status = self.status(self.jobs.get(id)?);
+        match status {
+            Status::Completed | Status::Failed => return Some(Err(status)),
+            Status::Cancelled => {}
+            Status::Pending | Status::Processing => {
+                if let Some(job) = self.jobs.get_mut(id) {
+                    job.cancelled_at = Some(SystemTime::now());
+                }
+            }
+        }
+        self.job(id).map(Ok)
+    }
+}
+
+fn millis(d: Duration) -> u64 {
+    u64::try_from(d.as_millis()).unwrap_or(u64::MAX)
+}
+
+/// Format `t` as an ISO 8601 UTC timestamp with millisecond precision.
+pub(crate) fn iso8601(t: SystemTime) -> String {
+    let since_epoch = t.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs();
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+/// Convert days since 1970-01-01 to a (year, month, day) civil date.
+///
+/// Howard Hinnant's `civil_from_days`, restricted to dates after the epoch.
+const fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::JobSchedule;
+
+    #[test]
+    fn formats_iso8601() {
+        let t = UNIX_EPOCH + Duration::from_millis(1_705_314_600_123);
+        assert_eq!(iso8601(t), "2024-01-15T10:30:00.123Z");
+        assert_eq!(iso8601(UNIX_EPOCH), "1970-01-01T00:00:00.000Z");
+        let leap = UNIX_EPOCH + Duration::from_hours(264_384);
+        assert_eq!(iso8601(leap), "2000-02-29T00:00:00.000Z");
+    }
+
+    #[test]
+    fn job_follows_schedule() {
+        let mut state = State::new(MockConfig {
+            schedule: JobSchedule {
+                processing_after: Duration::from_hours(1),
+                ..JobSchedule::immediate()
+            },
+            ..MockConfig::default()
+        });
+        let created = state.create_job("hi".to_owned(), None, None);
+        let id = created["jobId"].as_str().unwrap_or_default().to_owned();
+        assert_eq!(state.job(&id).map(|j| j["status"].clone()), Some(json!("pending")));
+
+        state.config.schedule = JobSchedule::immediate();
+        let job = state.job(&id).unwrap_or_default();
+        assert_eq!(job["status"], "completed");
+        assert_eq!(job["response"], "Mock response to: hi");
+        assert!(matches!(state.cancel_job(&id), Some(Err(Status::Completed))));
+    }
+
+    #[test]
+    fn cancel_pending_job() {
+        let mut state = State::new(MockConfig {
+            schedule: JobSchedule {
+                processing_after: Duration::from_hours(1),
+                ..JobSchedule::immediate()
+            },
+            ..MockConfig::default()
+        });
+        let created = state.create_job("hi".to_owned(), Some("thr_x".to_owned()), None);
+        let id = created["jobId"].as_str().unwrap_or_default().to_owned();
+        let cancelled = state.cancel_job(&id).and_then(Result::ok).unwrap_or_default();
+        assert_eq!(cancelled["status"], "cancelled");
+        assert_eq!(cancelled["threadId"], "thr_x");
+        assert!(state.cancel_job("missing").is_none());
+    }
+}
@@ -0,0 +1,392 @@
+//! End-to-end tests of `BankrAgentClient` against the mock server.
+
+#![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+
+use std::time::Duration;
+
+use bankr_agent_api::{
+    BankrAgentClient,
+    error::{BankrError, CancelOutcome},
+    llm::{ChatCompletionRequest, ChatMessage, LlmClient, Role},
+    primitives::PrimitiveError,
+    retry::RetryPolicy,
+    types::{EvmTransaction, JobStatus, PromptRequest, SignRequest, SubmitRequest},
+};
+use bankr_agent_api_mock::{DEFAULT_EVM_ADDRESS, Failure, JobSchedule, MockConfig, MockServer};
+use futures_util::TryStreamExt;
+
+fn client(server: &MockServer) -> BankrAgentClient {
+    BankrAgentClient::builder("bk_test")
+        .base_url(&server.url())
+        .connect_timeout(Duration::from_secs(5))
+        .retry_policy(RetryPolicy::none())
+        .poll_interval(Duration::from_millis(20))
+        .max_poll_attempts(50)
+        .build()
+        .unwrap()
+}
+
+fn llm(server: &MockServer) -> LlmClient {
+    LlmClient::builder("bk_llm")
+        .base_url(&server.url())
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap()
+}
+
+fn chat(text: &str) -> ChatCompletionRequest {
+    ChatCompletionRequest::new(
+        "claude-sonnet-4.5",
+        vec![ChatMessage::system("Be brief."), ChatMessage::user(text)],
+    )
+}
+
+fn prompt(text: &str) -> PromptRequest {
+    PromptRequest { prompt: text.to_owned(), thread_id: None }
+}
+
+fn transfer() -> EvmTransaction {
+    EvmTransaction {
+        to: "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913".to_owned(),
+        chain_id: 8453,
+        value: Some("0".to_owned()),
+        data: None,
+        gas: None,
+        gas_price: None,
+        max_fee_per_gas: None,
+        max_priority_fee_per_gas: None,
+        nonce: None,
+    }
+}
+
+#[tokio::test]
+async fn get_me_returns_wallets() {
+    let server = MockServer::start().await.unwrap();
+    let me = client(&server).get_me().await.unwrap();
+
+    assert!(me.success);
+    assert_eq!(me.wallets[0].address, DEFAULT_EVM_ADDRESS);
+
+    let requests = server.requests();
+    assert_eq!(requests[0].path, "/agent/me");
+    assert_eq!(requests[0].api_key.as_deref(), Some("bk_test"));
+}
+
+#[tokio::test]
+async fn wrong_api_key_is_unauthorized() {
+    let config = MockConfig { api_key: Some("bk_other".to_owned()), ..MockConfig::default() };
+    let server = MockServer::start_with(config).await.unwrap();
+
+    let err = client(&server).get_me().await.unwrap_err();
+    assert!(matches!(err, BankrError::Unauthorized { .. }), "{err:?}");
+}
+
+#[tokio::test]
+async fn job_moves_through_schedule() {
+    let config = MockConfig {
+        schedule: JobSchedule {
+            processing_after: Duration::from_millis(100),
+            completed_after: Duration::from_millis(100),
+        },
+        ..MockConfig::default()
+    };
+    let server = MockServer::start_with(config).await.unwrap();
+    let client = client(&server);
+
+    let created = client.submit_prompt(&prompt("price of ETH")).await.unwrap();
+    assert_eq!(created.status, "pending");
+    assert_eq!(client.get_job(&created.job_id).await.unwrap().status, JobStatus::Pending);
+
+    let job = client.prompt_and_wait(&prompt("price of ETH")).await.unwrap();
+    assert_eq!(job.status, JobStatus::Completed);
+    assert_eq!(job.response.as_deref(), Some("Mock response to: price of ETH"));
+    assert!(job.completed_at.is_some());
+}
+
+#[tokio::test]
+async fn thread_id_is_kept() {
+    let server = MockServer::start_with(MockConfig {
+        schedule: JobSchedule::immediate(),
+        ..MockConfig::default()
+    })
+    .await
+    .unwrap();
+
+    let req =
+        PromptRequest { prompt: "and SOL?".to_owned(), thread_id: Some("thr_abc".to_owned()) };
+    let job = client(&server).prompt_and_wait(&req).await.unwrap();
+    assert_eq!(job.thread_id.as_deref(), Some("thr_abc"));
+}
+
+#[tokio::test]
+async fn cancel_and_cancel_completed() {
+    let config = MockConfig {
+        schedule: JobSchedule {
+            processing_after: Duration::from_hours(1),
+            ..JobSchedule::immediate()
+        },
+        ..MockConfig::default()
+    };
+    let server = MockServer::start_with(config).await.unwrap();
+    let client = client(&server);
+
+    let created = client.submit_prompt(&prompt("swap")).await.unwrap();
+    let cancelled = client.cancel_job(&created.job_id).await.unwrap();
+    assert_eq!(cancelled.status, "cancelled");
+    assert!(cancelled.cancelled_at.is_some());
+
+    let err = client.poll_job(&created.job_id, Duration::ZERO, 1).await.unwrap_err();
+    assert!(matches!(err, BankrError::JobCancelled));
+
+    let err = client.get_job("job_missing").await.unwrap_err();
+    assert!(matches!(err, BankrError::NotFound { .. }));
+
+    let done = MockServer::start_with(MockConfig {
+        schedule: JobSchedule::immediate(),
+        ..MockConfig::default()
+    })
+    .await
+    .unwrap();
+    let client = self::client(&done);
+    let created = client.submit_prompt(&prompt("swap")).await.unwrap();
+    let err = client.cancel_job(&created.job_id).await.unwrap_err();
+    assert!(matches!(err, BankrError::JobNotCancellable { .. }), "{err:?}");
+}
+
+#[tokio::test]
+async fn interrupted_wait_cancels_job() {
+    let config = MockConfig {
+        schedule: JobSchedule {
+            processing_after: Duration::from_hours(1),
+            ..JobSchedule::immediate()
+        },
+        ..MockConfig::default()
+    };
+    let server = MockServer::start_with(config).await.unwrap();
+    let client = client(&server);
+
+    let signal = tokio::time::sleep(Duration::from_millis(50));
+    let err = client.prompt_and_wait_or_cancel(&prompt("swap"), signal).await.unwrap_err();
+    let BankrError::Interrupted { job_id, outcome: CancelOutcome::Cancelled } = err else {
+        panic!("unexpected error: {err:?}");
+    };
+    assert_eq!(client.get_job(&job_id).await.unwrap().status, JobStatus::Cancelled);
+}
+
+#[tokio::test]
+async fn failed_job() {
+    let server = MockServer::start_with(MockConfig {
+        schedule: JobSchedule::immediate(),
+        ..MockConfig::default()
+    })
+    .await
+    .unwrap();
+    server.inject(Failure::JobFailed("Insufficient balance".to_owned()));
+
+    let err = client(&server).prompt_and_wait(&prompt("swap")).await.unwrap_err();
+    assert!(
+        matches!(err, BankrError::JobFailed { ref message } if message == "Insufficient balance")
+    );
+}
+
+#[tokio::test]
+async fn injected_unauthorized_is_one_shot() {
+    let server = MockServer::start().await.unwrap();
+    server.inject(Failure::Unauthorized);
+    let client = client(&server);
+
+    assert!(matches!(client.get_me().await, Err(BankrError::Unauthorized { .. })));
+    assert!(client.get_me().await.is_ok());
+}
+
+#[tokio::test]
+async fn read_only_key_applies_to_sign_and_submit() {
+    let server = MockServer::start().await.unwrap();
+    server.inject_times(Failure::ReadOnly, 2);
+    let client = client(&server);
+
+    // Not consumed by read endpoints.
+    client.get_me().await.unwrap();
+
+    let req = SignRequest::personal("hello");
+    let err = client.sign(&req).await.unwrap_err();
+    assert!(matches!(err, BankrError::ReadOnlyKey { .. }), "{err:?}");
+
+    let submit =
+        SubmitRequest { transaction: transfer(), description: None, wait_for_confirmation: None };
+    let err = client.submit_transaction(&submit).await.unwrap_err();
+    assert!(matches!(err, BankrError::ReadOnlyKey { .. }), "{err:?}");
+
+    let signed = client.sign(&req).await.unwrap();
+    assert_eq!(signed.signature.as_ref().map(String::len), Some(132));
+    assert_eq!(signed.signer.as_deref(), Some(DEFAULT_EVM_ADDRESS));
+    assert_eq!(
+        signed.signer_address().map(|a| a.to_string()).as_deref(),
+        Some(DEFAULT_EVM_ADDRESS)
+    );
+}
+
+#[tokio::test]
+async fn malformed_transaction_is_rejected_before_sending() {
+    let server = MockServer::start().await.unwrap();
+    let submit = SubmitRequest {
+        transaction: EvmTransaction { data: Some("1234".to_owned()), ..transfer() },
+        description: None,
+        wait_for_confirmation: None,
+    };
+
+    let err = client(&server).submit_transaction(&submit).await.unwrap_err();
+    assert!(matches!(err, BankrError::InvalidInput(PrimitiveError::InvalidHex(_))), "{err:?}");
+    assert!(server.requests().is_empty());
+}
+
+#[tokio::test]
+async fn rate_limited_prompt_reports_reset() {
+    let server = MockServer::start().await.unwrap();
+    server.inject(Failure::RateLimited { reset_at: 1_736_942_400_000 });
+
+    let err = client(&server).submit_prompt(&prompt("hi")).await.unwrap_err();
+    assert!(
+        matches!(
+            err,
+            BankrError::RateLimited { reset_at: Some(1_736_942_400_000), limit: Some(100), .. }
+        ),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn server_errors_are_retried_for_idempotent_calls() {
+    let server = MockServer::start().await.unwrap();
+    server.inject(Failure::ServerError);
+
+    let client = BankrAgentClient::builder("bk_test")
+        .base_url(&server.url())
+        .retry_policy(
+            RetryPolicy::default().backoff(Duration::from_millis(1), Duration::from_millis(1)),
+        )
+        .build()
+        .unwrap();
+    client.get_me().await.unwrap();
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn submit_success_and_revert() {
+    let server = MockServer::start().await.unwrap();
+    let client = client(&server);
+    let req = SubmitRequest {
+        transaction: transfer(),
+        description: Some("test".to_owned()),
+        wait_for_confirmation: Some(true),
+    };
+
+    let ok = client.submit_transaction(&req).await.unwrap();
+    assert_eq!(ok.status.as_deref(), Some("success"));
+    assert_eq!(ok.chain_id, Some(8453));
+    assert!(ok.block_number.is_some());
+
+    server.inject(Failure::RevertedSubmit);
+    let reverted = client.submit_transaction(&req).await.unwrap();
+    assert_eq!(reverted.status.as_deref(), Some("reverted"));
+    assert!(reverted.transaction_hash.is_some());
+
+    let pending = client
+        .submit_transaction(&SubmitRequest { wait_for_confirmation: Some(false), ..req })
+        .await
+        .unwrap();
+    assert_eq!(pending.status.as_deref(), Some("pending"));
+    assert!(pending.block_number.is_none());
+}
+
+#[tokio::test]
+async fn invalid_sign_request_is_rejected() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    // `SignRequest` cannot express a missing payload, so send the JSON by hand.
+    let server = MockServer::start().await.unwrap();
+    let body = r#"{"signatureType":"eth_signTransaction"}"#;
+    let mut stream = tokio::net::TcpStream::connect(server.addr()).await.unwrap();
+    let request = format!(
+        "POST /agent/sign HTTP/1.1\r\nHost: localhost\r\nX-API-Key: bk_test\r\n\
+         Content-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+
+    assert!(response.starts_with("HTTP/1.1 400"), "{response}");
+    assert!(response.contains("transaction object is required"), "{response}");
+}
+
+#[tokio::test]
+async fn llm_chat_completion() {
+    let server = MockServer::start().await.unwrap();
+    let completion = llm(&server).chat_completion(&chat("gm anon")).await.unwrap();
+
+    assert_eq!(completion.model, "claude-sonnet-4.5");
+    assert_eq!(completion.choices[0].message.role, Role::Assistant);
+    assert_eq!(completion.text(), Some("Echo: gm anon"));
+    let usage = completion.usage.unwrap();
+    assert_eq!((usage.prompt_tokens, usage.completion_tokens, usage.total_tokens), (4, 3, 7));
+
+    let requests = server.requests();
+    assert_eq!(requests[0].path, "/v1/chat/completions");
+    assert_eq!(requests[0].api_key.as_deref(), Some("bk_llm"));
+    assert_eq!(requests[0].body["stream"], false);
+    assert_eq!(requests[0].body["messages"][0]["role"], "system");
+}
+
+#[tokio::test]
+async fn llm_chat_completion_stream() {
+    let server = MockServer::start().await.unwrap();
+    let stream = llm(&server).chat_completion_stream(&chat("gm anon")).await.unwrap();
+    let chunks: Vec<_> = stream.try_collect().await.unwrap();
+
+    let text: String = chunks.iter().map(|c| c.text()).collect();
+    assert_eq!(text, "Echo: gm anon");
+    assert_eq!(chunks[0].choices[0].delta.role, Some(Role::Assistant));
+    assert_eq!(chunks.last().and_then(|c| c.usage.as_ref()).map(|u| u.total_tokens), Some(7));
+    assert_eq!(server.requests()[0].body["stream_options"]["include_usage"], true);
+}
+
+#[tokio::test]
+async fn llm_stream_errors_and_retries() {
+    let server = MockServer::start().await.unwrap();
+    server.inject(Failure::RateLimited { reset_at: 1_736_942_400_000 });
+    let err = llm(&server).chat_completion_stream(&chat("hi")).await.err().unwrap();
+    assert!(matches!(err, BankrError::RateLimited { .. }), "{err:?}");
+
+    // Completions are billed, so a server error is only retried on request.
+    let policy = RetryPolicy::default().backoff(Duration::from_millis(1), Duration::from_millis(1));
+    server.inject(Failure::ServerError);
+    let client = LlmClient::builder("bk_llm")
+        .base_url(&server.url())
+        .retry_policy(policy.clone())
+        .build()
+        .unwrap();
+    let err = client.chat_completion_stream(&chat("hi")).await.err().unwrap();
+    assert!(matches!(err, BankrError::Api { status: 500, .. }), "{err:?}");
+    assert_eq!(server.requests().len(), 2);
+
+    server.inject(Failure::ServerError);
+    let client = LlmClient::builder("bk_llm")
+        .base_url(&server.url())
+        .retry_policy(policy.retry_non_idempotent(true))
+        .build()
+        .unwrap();
+    let chunks: Vec<_> =
+        client.chat_completion_stream(&chat("hi")).await.unwrap().try_collect().await.unwrap();
+    assert!(!chunks.is_empty());
+    assert_eq!(server.requests().len(), 4);
+}
+
+#[tokio::test]
+async fn llm_list_models() {
+    let server = MockServer::start().await.unwrap();
+    let models = llm(&server).list_models().await.unwrap();
+
+    assert!(models.iter().any(|m| m.id == "claude-sonnet-4.5"));
+    assert_eq!(models[0].owned_by.as_deref(), Some("anthropic"));
+}
@@ -0,0 +1,325 @@
+//! The [`BankrApi`] trait, implemented by [`BankrAgentClient`] and
+//! [`MockBankrApi`](crate::mock::MockBankrApi).
+//!
+//! Code that depends on `BankrApi` instead of the concrete client can be unit
+//! tested without HTTP:
+//!
+//! ```rust
+//! use bankr_agent_api::{BankrApi, error::BankrError, types::UserInfoResponse};
+//!
+//! async fn evm_wallet(api: &impl BankrApi) -> Result<Option<String>, BankrError> {
+//!     let me: UserInfoResponse = api.get_me().await?;
+//!     Ok(me.wallets.into_iter().find(|w| w.chain == "evm").map(|w| w.address))
+//! }
+//! ```
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures_util::future::{self, Either};
+use tracing::{debug, info, warn};
+
+use crate::{
+    BankrAgentClient,
+    error::{BankrError, CancelOutcome},
+    poll::{PollConfig, PollSchedule},
+    types::{
+        CancelJobResponse, JobResponse, JobStatus, PromptRequest, PromptResponse, SignRequest,
+        SignResponse, SubmitRequest, SubmitResponse, UserInfoResponse,
+    },
+};
+
+/// The Bankr Agent API operations.
+#[async_trait]
+pub trait BankrApi: Send + Sync {
+    /// Retrieve the authenticated user's profile (`GET /agent/me`).
+    async fn get_me(&self) -> Result<UserInfoResponse, BankrError>;
+
+    /// Submit a natural language prompt (`POST /agent/prompt`).
+    async fn submit_prompt(&self, req: &PromptRequest) -> Result<PromptResponse, BankrError>;
+
+    /// Get the status of a job (`GET /agent/job/{jobId}`).
+    async fn get_job(&self, job_id: &str) -> Result<JobResponse, BankrError>;
+
+    /// Cancel a pending or processing job (`POST /agent/job/{jobId}/cancel`).
+    async fn cancel_job(&self, job_id: &str) -> Result<CancelJobResponse, BankrError>;
+
+    /// Sign a message, typed data, or transaction (`POST /agent/sign`).
+    async fn sign(&self, req: &SignRequest) -> Result<SignResponse, BankrError>;
+
+    /// Submit a raw EVM transaction (`POST /agent/submit`).
+    async fn submit_transaction(&self, req: &SubmitRequest) -> Result<SubmitResponse, BankrError>;
+
+    /// Poll a job every `interval` until it reaches a terminal state, at most
+    /// `max_attempts` times.
+    async fn poll_job(
+        &self,
+        job_id: &str,
+        interval: Duration,
+        max_attempts: u32,
+    ) -> Result<JobResponse, BankrError> {
+        poll_job(self, job_id, &PollConfig::fixed(interval, max_attempts)).await
+    }
+
+    /// Poll a job until it reaches a terminal state, as configured by
+    /// `config`.
+    async fn poll_job_with(
+        &self,
+        job_id: &str,
+        config: &PollConfig,
+    ) -> Result<JobResponse, BankrError> {
+        poll_job(self, job_id, config).await
+    }
+}
+
+/// Poll `job_id` via [`BankrApi::get_job`] until it reaches a terminal state.
+pub(crate) async fn poll_job<A: BankrApi + ?Sized>(
+    api: &A,
+    job_id: &str,
+    config: &PollConfig,
+) -> Result<JobResponse, BankrError> {
+    poll_until_terminal(api, job_id, config).await.and_then(completed)
+}
+
+/// The job if it completed, else [`BankrError::JobFailed`] or
+/// [`BankrError::JobCancelled`].
+pub(crate) fn completed(job: JobResponse) -> Result<JobResponse, BankrError> {
+    match job.status {
+        JobStatus::Failed => Err(BankrError::JobFailed {
+            message: job.error.unwrap_or_else(|| "unknown error".to_owned()),
+        }),
+        JobStatus::Cancelled => Err(BankrError::JobCancelled),
+        _ => Ok(job),
+    }
+}
+
+/// Like [`poll_job`], but failed and cancelled jobs are returned as `Ok`.
+pub(crate) async fn poll_until_terminal<A: BankrApi + ?Sized>(
+    api: &A,
+    job_id: &str,
+    config: &PollConfig,
+) -> Result<JobResponse, BankrError> {
+    let mut schedule = PollSchedule::new(config);
+    loop {
+        let job = api.get_job(job_id).await?;
+        debug!(attempt = schedule.attempts() + 1, status = %job.status, "Poll attempt");
+        if job.status.is_terminal() {
+            return Ok(job);
+        }
+
+        let Some(delay) = schedule.after_attempt() else {
+            warn!(job_id, attempts = schedule.attempts(), "Poll timeout reached");
+            return Err(BankrError::PollTimeout {
+                attempts: schedule.attempts(),
+                last_job: Some(Box::new(job)),
+            });
+        };
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// Poll `job_id` like [`poll_job`], but stop as soon as `signal` resolves and
+/// cancel the job if it is still cancellable.
+///
+/// An interrupted wait returns [`BankrError::Interrupted`] describing what
+/// happened to the server-side job.
+pub(crate) async fn poll_job_or_cancel<A, F>(
+    api: &A,
+    job_id: &str,
+    config: &PollConfig,
+    signal: F,
+) -> Result<JobResponse, BankrError>
+where
+    A: BankrApi + ?Sized,
+    F: Future<Output = ()>,
+{
+    let signal = std::pin::pin!(signal);
+    let poll = std::pin::pin!(poll_job(api, job_id, config));
+
+    match future::select(signal, poll).await {
+        Either::Left(((), _)) => {
+            info!(job_id, "Wait interrupted, cancelling job");
+            let outcome = cancel_if_cancellable(api, job_id).await;
+            Err(BankrError::Interrupted { job_id: job_id.to_owned(), outcome })
+        }
+        Either::Right((result, _)) => result,
+    }
+}
+
+/// Cancel `job_id` unless it has already reached a terminal state.
+///
+/// If the job's state cannot be fetched, cancelling is attempted anyway.
+async fn cancel_if_cancellable<A: BankrApi + ?Sized>(api: &A, job_id: &str) -> CancelOutcome {
+    match api.get_job(job_id).await {
+        Ok(job) => {
+            let cancellable = job
+                .cancellable
+                .unwrap_or(matches!(job.status, JobStatus::Pending | JobStatus::Processing));
+            if !cancellable {
+                return CancelOutcome::AlreadyFinished(Box::new(job));
+            }
+        }
+        Err(e) => warn!(job_id, error = %e, "Could not fetch job state, cancelling anyway"),
+    }
+
+    match api.cancel_job(job_id).await {
+        Ok(_) => CancelOutcome::Cancelled,
+        // The job finished between the status check and the cancel request.
+        Err(BankrError::JobNotCancellable { .. }) => match api.get_job(job_id).await {
+            Ok(job) => CancelOutcome::AlreadyFinished(Box::new(job)),
+            Err(e) => CancelOutcome::CancelFailed(Box::new(e)),
+        },
+        Err(e) => CancelOutcome::CancelFailed(Box::new(e)),
+    }
+}
+
+#[async_trait]
+impl BankrApi for BankrAgentClient {
+    async fn get_me(&self) -> Result<UserInfoResponse, BankrError> {
+        Self::get_me(self).await
+    }
+
+    async fn submit_prompt(&self, req: &PromptRequest) -> Result<PromptResponse, BankrError> {
+        Self::submit_prompt(self, req).await
+    }
+
+    async fn get_job(&self, job_id: &str) -> Result<JobResponse, BankrError> {
+        Self::get_job(self, job_id).await
+    }
+
+    async fn cancel_job(&self, job_id: &str) -> Result<CancelJobResponse, BankrError> {
+        Self::cancel_job(self, job_id).await
+    }
+
+    async fn sign(&self, req: &SignRequest) -> Result<SignResponse, BankrError> {
+        Self::sign(self, req).await
+    }
+
+    async fn submit_transaction(&self, req: &SubmitRequest) -> Result<SubmitResponse, BankrError> {
+        Self::submit_transaction(self, req).await
+    }
+
+    async fn poll_job(
+        &self,
+        job_id: &str,
+        interval: Duration,
+        max_attempts: u32,
+    ) -> Result<JobResponse, BankrError> {
+        Self::poll_job(self, job_id, interval, max_attempts).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mock::MockBankrApi, types::JobStatus};
+
+    fn job(status: JobStatus, cancellable: bool) -> JobResponse {
+        JobResponse { cancellable: Some(cancellable), ..JobResponse::new("job_1", status) }
+    }
+
+    fn cancelled() -> CancelJobResponse {
+        CancelJobResponse {
+            success: true,
+            job_id: "job_1".to_owned(),
+            status: "cancelled".to_owned(),
+            prompt: None,
+            created_at: None,
+            cancelled_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn timeout_keeps_last_job() {
+        let mock = MockBankrApi::new();
+        mock.push_get_job(Ok(job(JobStatus::Pending, true)));
+        mock.push_get_job(Ok(job(JobStatus::Processing, true)));
+
+        let err = poll_job(&mock, "job_1", &PollConfig::fixed(Duration::ZERO, 2)).await.err();
+        assert!(matches!(err, Some(BankrError::PollTimeout { attempts: 2, .. })));
+        let last = err.as_ref().and_then(BankrError::last_job);
+        assert_eq!(last.map(|j| &j.status), Some(&JobStatus::Processing));
+        assert_eq!(last.map(|j| j.job_id.as_str()), Some("job_1"));
+    }
+
+    #[tokio::test]
+    async fn signal_cancels_running_job() {
+        let mock = MockBankrApi::new();
+        mock.push_get_job(Ok(job(JobStatus::Processing, true)));
+        mock.push_cancel_job(Ok(cancelled()));
+
+        let err =
+            poll_job_or_cancel(&mock, "job_1", &PollConfig::fixed(Duration::ZERO, 5), async {})
+                .await;
+        assert!(matches!(
+            err,
+            Err(BankrError::Interrupted { ref job_id, outcome: CancelOutcome::Cancelled })
+                if job_id == "job_1"
+        ));
+        assert!(mock.calls().iter().any(|c| matches!(c, crate::mock::MockCall::CancelJob(_))));
+    }
+
+    #[tokio::test]
+    async fn failed_status_check_still_cancels() {
+        let mock = MockBankrApi::new();
+        mock.push_get_job(Err(BankrError::Transport("timed out".to_owned())));
+        mock.push_cancel_job(Ok(cancelled()));
+
+        let err =
+            poll_job_or_cancel(&mock, "job_1", &PollConfig::fixed(Duration::ZERO, 5), async {})
+                .await;
+        assert!(matches!(
+            err,
+            Err(BankrError::Interrupted { outcome: CancelOutcome::Cancelled, .. })
+        ));
+        assert!(mock.calls().iter().any(|c| matches!(c, crate::mock::MockCall::CancelJob(_))));
+    }
+
+    #[tokio::test]
+    async fn finished_job_is_not_cancelled() {
+        let mock = MockBankrApi::new();
+        mock.push_get_job(Ok(job(JobStatus::Completed, false)));
+
+        let err =
+            poll_job_or_cancel(&mock, "job_1", &PollConfig::fixed(Duration::ZERO, 5), async {})
+                .await;
+        assert!(matches!(
+            err,
+            Err(BankrError::Interrupted { outcome: CancelOutcome::AlreadyFinished(ref job), .. })
+                if job.status == JobStatus::Completed
+        ));
+        assert_eq!(mock.calls().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn race_with_completion_reports_final_state() {
+        let mock = MockBankrApi::new();
+        mock.push_get_job(Ok(job(JobStatus::Processing, true)));
+        mock.push_cancel_job(Err(BankrError::JobNotCancellable { body: Default::default() }));
+        mock.push_get_job(Ok(job(JobStatus::Completed, false)));
+
+        let err =
+            poll_job_or_cancel(&mock, "job_1", &PollConfig::fixed(Duration::ZERO, 5), async {})
+                .await;
+        assert!(matches!(
+            err,
+            Err(BankrError::Interrupted { outcome: CancelOutcome::AlreadyFinished(_), .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn pending_signal_does_not_interrupt() -> Result<(), BankrError> {
+        let mock = MockBankrApi::new();
+        mock.push_get_job(Ok(job(JobStatus::Completed, false)));
+
+        let done = poll_job_or_cancel(
+            &mock,
+            "job_1",
+            &PollConfig::fixed(Duration::ZERO, 5),
+            std::future::pending(),
+        )
+        .await?;
+        assert_eq!(done.status, JobStatus::Completed);
+        Ok(())
+    }
+}
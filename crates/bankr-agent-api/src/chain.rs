@@ -0,0 +1,259 @@
+//! Registry of the EVM networks Bankr supports.
+//!
+//! Chain IDs travel as bare `u64`s on the wire ([`EvmTransaction::chain_id`],
+//! [`SubmitResponse::chain_id`]); [`Chain`] adds names, aliases, native token
+//! metadata and block explorer links on top.
+//!
+//! ```rust
+//! use bankr_agent_api::chain::Chain;
+//!
+//! let base: Chain = "base".parse()?;
+//! assert_eq!(base.id(), 8453);
+//! assert_eq!(base.native_symbol(), "ETH");
+//! assert_eq!(base.tx_url("0xabc"), "https://basescan.org/tx/0xabc");
+//! # Ok::<(), bankr_agent_api::chain::UnknownChain>(())
+//! ```
+
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{EvmTransaction, SubmitResponse};
+
+/// A network supported by `/agent/submit` and `/agent/sign`.
+///
+/// Serializes as its numeric chain ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "u64", try_from = "u64")]
+pub enum Chain {
+    /// Ethereum mainnet (1).
+    Ethereum,
+    /// Base (8453).
+    Base,
+    /// Polygon PoS (137).
+    Polygon,
+    /// Unichain (130).
+    Unichain,
+}
+
+/// Static metadata for a [`Chain`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainInfo {
+    /// EIP-155 chain ID.
+    pub id: u64,
+    /// Display name.
+    pub name: &'static str,
+    /// Lowercase names accepted by [`Chain::from_str`], canonical first.
+    pub aliases: &'static [&'static str],
+    /// Symbol of the native gas token.
+    pub native_symbol: &'static str,
+    /// Decimals of the native gas token.
+    pub native_decimals: u8,
+    /// Block explorer transaction page; `{hash}` is replaced.
+    pub tx_url_template: &'static str,
+    /// Block explorer address page; `{address}` is replaced.
+    pub address_url_template: &'static str,
+}
+
+const ETHEREUM: ChainInfo = ChainInfo {
+    id: 1,
+    name: "Ethereum",
+    aliases: &["ethereum", "eth", "mainnet"],
+    native_symbol: "ETH",
+    native_decimals: 18,
+    tx_url_template: "https://etherscan.io/tx/{hash}",
+    address_url_template: "https://etherscan.io/address/{address}",
+};
+
+const BASE: ChainInfo = ChainInfo {
+    id: 8453,
+    name: "Base",
+    aliases: &["base"],
+    native_symbol: "ETH",
+    native_decimals: 18,
+    tx_url_template: "https://basescan.org/tx/{hash}",
+    address_url_template: "https://basescan.org/address/{address}",
+};
+
+const POLYGON: ChainInfo = ChainInfo {
+    id: 137,
+    name: "Polygon",
+    aliases: &["polygon", "matic", "pol"],
+    native_symbol: "POL",
+    native_decimals: 18,
+    tx_url_template: "https://polygonscan.com/tx/{hash}",
+    address_url_template: "https://polygonscan.com/address/{address}",
+};
+
+const UNICHAIN: ChainInfo = ChainInfo {
+    id: 130,
+    name: "Unichain",
+    aliases: &["unichain", "uni"],
+    native_symbol: "ETH",
+    native_decimals: 18,
+    tx_url_template: "https://uniscan.xyz/tx/{hash}",
+    address_url_template: "https://uniscan.xyz/address/{address}",
+};
+
+impl Chain {
+    /// Every supported chain.
+    pub const ALL: [Self; 4] = [Self::Ethereum, Self::Base, Self::Polygon, Self::Unichain];
+
+    /// This chain's metadata.
+    pub const fn info(self) -> &'static ChainInfo {
+        match self {
+            Self::Ethereum => &ETHEREUM,
+            Self::Base => &BASE,
+            Self::Polygon => &POLYGON,
+            Self::Unichain => &UNICHAIN,
+        }
+    }
+
+    /// Look up a chain by ID.
+    pub const fn from_id(id: u64) -> Option<Self> {
+        match id {
+            1 => Some(Self::Ethereum),
+            8453 => Some(Self::Base),
+            137 => Some(Self::Polygon),
+            130 => Some(Self::Unichain),
+            _ => None,
+        }
+    }
+
+    /// EIP-155 chain ID.
+    pub const fn id(self) -> u64 {
+        self.info().id
+    }
+
+    /// Display name, e.g. `"Base"`.
+    pub const fn name(self) -> &'static str {
+        self.info().name
+    }
+
+    /// Symbol of the native gas token, e.g. `"ETH"`.
+    pub const fn native_symbol(self) -> &'static str {
+        self.info().native_symbol
+    }
+
+    /// Decimals of the native gas token.
+    pub const fn native_decimals(self) -> u8 {
+        self.info().native_decimals
+    }
+
+    /// Block explorer link for a transaction hash.
+    pub fn tx_url(self, hash: &str) -> String {
+        self.info().tx_url_template.replace("{hash}", hash)
+    }
+
+    /// Block explorer link for an address.
+    pub fn address_url(self, address: &str) -> String {
+        self.info().address_url_template.replace("{address}", address)
+    }
+}
+
+impl fmt::Display for Chain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A chain name or ID that is not in the registry.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("unsupported chain {0:?} (supported: ethereum/1, base/8453, polygon/137, unichain/130)")]
+pub struct UnknownChain(pub String);
+
+/// Parses a name or alias (case-insensitive) or a numeric chain ID.
+impl FromStr for Chain {
+    type Err = UnknownChain;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|chain| chain.info().aliases.contains(&lower.as_str()))
+            .or_else(|| lower.parse().ok().and_then(Self::from_id))
+            .ok_or_else(|| UnknownChain(s.to_owned()))
+    }
+}
+
+impl TryFrom<u64> for Chain {
+    type Error = UnknownChain;
+
+    fn try_from(id: u64) -> Result<Self, Self::Error> {
+        Self::from_id(id).ok_or_else(|| UnknownChain(id.to_string()))
+    }
+}
+
+impl From<Chain> for u64 {
+    fn from(chain: Chain) -> Self {
+        chain.id()
+    }
+}
+
+impl EvmTransaction {
+    /// The registry entry for [`chain_id`](Self::chain_id), if supported.
+    pub const fn chain(&self) -> Option<Chain> {
+        Chain::from_id(self.chain_id)
+    }
+}
+
+impl SubmitResponse {
+    /// The registry entry for [`chain_id`](Self::chain_id), if reported and
+    /// supported.
+    pub fn chain(&self) -> Option<Chain> {
+        self.chain_id.and_then(Chain::from_id)
+    }
+
+    /// Block explorer link for the submitted transaction.
+    pub fn explorer_url(&self) -> Option<String> {
+        Some(self.chain()?.tx_url(self.transaction_hash.as_deref()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_names_aliases_and_ids() {
+        assert_eq!("base".parse(), Ok(Chain::Base));
+        assert_eq!("ETH".parse(), Ok(Chain::Ethereum));
+        assert_eq!("Polygon".parse(), Ok(Chain::Polygon));
+        assert_eq!("130".parse(), Ok(Chain::Unichain));
+        assert!("solana".parse::<Chain>().is_err());
+        assert!("10".parse::<Chain>().is_err());
+    }
+
+    #[test]
+    fn registry_is_consistent() {
+        for chain in Chain::ALL {
+            assert_eq!(Chain::from_id(chain.id()), Some(chain));
+            assert_eq!(chain.info().aliases[0].parse(), Ok(chain));
+            assert_eq!(chain.native_decimals(), 18);
+        }
+    }
+
+    #[test]
+    fn serializes_as_id() -> Result<(), serde_json::Error> {
+        assert_eq!(serde_json::to_string(&Chain::Base)?, "8453");
+        assert_eq!(serde_json::from_str::<Chain>("137")?, Chain::Polygon);
+        assert!(serde_json::from_str::<Chain>("10").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn explorer_links() {
+        let resp = SubmitResponse {
+            success: true,
+            transaction_hash: Some("0xabc".to_owned()),
+            status: None,
+            block_number: None,
+            gas_used: None,
+            signer: None,
+            chain_id: Some(1),
+            error: None,
+        };
+        assert_eq!(resp.explorer_url().as_deref(), Some("https://etherscan.io/tx/0xabc"));
+        assert_eq!(Chain::Unichain.address_url("0x1"), "https://uniscan.xyz/address/0x1");
+    }
+}
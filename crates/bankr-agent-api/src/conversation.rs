@@ -0,0 +1,269 @@
+//! Multi-turn conversations on a single Bankr thread.
+//!
+//! A [`Conversation`] starts a thread with its first prompt, sends follow-ups
+//! on the same `thread_id`, and keeps an ordered transcript of turns. It is
+//! plain data: serialize it to save a conversation and deserialize it to
+//! resume after a restart.
+//!
+//! ```rust,no_run
+//! use bankr_agent_api::{BankrAgentClient, conversation::Conversation};
+//!
+//! # async fn example(client: &BankrAgentClient) -> Result<(), Box<dyn std::error::Error>> {
+//! let mut convo = Conversation::new();
+//! convo.send(client, "what is the price of ETH?").await?;
+//! convo.send(client, "and SOL?").await?;
+//!
+//! let saved = serde_json::to_string(&convo)?;
+//! let mut convo: Conversation = serde_json::from_str(&saved)?;
+//! convo.send(client, "compare them").await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    BankrAgentClient,
+    api::BankrApi,
+    error::BankrError,
+    job::JobHandle,
+    poll::PollConfig,
+    types::{JobResponse, JobStatus, PromptRequest},
+};
+
+/// One prompt and the job it produced.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Turn {
+    /// The prompt sent.
+    pub prompt: String,
+    /// The last observed state of the job. Completed, failed or cancelled
+    /// once the turn has finished; still pending or processing if waiting
+    /// for it was cut short, in which case
+    /// [`Conversation::resume_turn_with`] (or a [`JobHandle`] for
+    /// `job.job_id`) picks it up again.
+    pub job: JobResponse,
+}
+
+impl Turn {
+    /// Whether the job reached a terminal state.
+    pub const fn is_finished(&self) -> bool {
+        self.job.status.is_terminal()
+    }
+}
+
+/// An ordered transcript of turns on one conversation thread.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Conversation {
+    thread_id: Option<String>,
+    turns: Vec<Turn>,
+}
+
+impl Conversation {
+    /// A new conversation; the first prompt starts a thread.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Continue an existing thread.
+    pub fn resume(thread_id: &str) -> Self {
+        Self { thread_id: Some(thread_id.to_owned()), turns: Vec::new() }
+    }
+
+    /// The thread ID, once the first prompt has been submitted.
+    pub fn thread_id(&self) -> Option<&str> {
+        self.thread_id.as_deref()
+    }
+
+    /// Turns, oldest first.
+    pub fn turns(&self) -> &[Turn] {
+        &self.turns
+    }
+
+    /// The agent's reply in the latest turn.
+    pub fn last_response(&self) -> Option<&str> {
+        self.turns.last().and_then(|turn| turn.job.response.as_deref())
+    }
+
+    /// Send `prompt` on this thread and wait for the reply, using the
+    /// client's [`PollConfig`].
+    pub async fn send(
+        &mut self,
+        client: &BankrAgentClient,
+        prompt: &str,
+    ) -> Result<&Turn, BankrError> {
+        self.send_with(client, prompt, client.poll_config()).await
+    }
+
+    /// Send `prompt` on this thread via any [`BankrApi`] and wait for the
+    /// reply, polling as configured by `poll`.
+    ///
+    /// The thread ID is recorded as soon as the prompt is accepted, so
+    /// follow-ups stay on the same thread even if this turn fails. Once the
+    /// prompt is accepted the turn is always added to the transcript, also
+    /// when an error is returned: a failed or cancelled job in its final
+    /// state, and a job whose wait ended early (e.g. a poll timeout or a
+    /// network error) in its last observed state, so that it can be resumed
+    /// with [`resume_turn_with`](Self::resume_turn_with).
+    pub async fn send_with<A: BankrApi + ?Sized>(
+        &mut self,
+        api: &A,
+        prompt: &str,
+        poll: &PollConfig,
+    ) -> Result<&Turn, BankrError> {
+        let req = PromptRequest { prompt: prompt.to_owned(), thread_id: self.thread_id.clone() };
+        let mut handle = JobHandle::submit(api, &req, poll.clone()).await?;
+        if let Some(thread_id) = handle.thread_id() {
+            self.thread_id = Some(thread_id.to_owned());
+        }
+
+        let result = handle.wait().await;
+        let job = handle.last().cloned().unwrap_or_else(|| JobResponse {
+            thread_id: handle.thread_id().map(str::to_owned),
+            prompt: prompt.to_owned(),
+            ..JobResponse::new(handle.job_id(), JobStatus::Pending)
+        });
+        self.turns.push(Turn { prompt: prompt.to_owned(), job });
+        result?;
+        Ok(&self.turns[self.turns.len() - 1])
+    }
+
+    /// Wait again for the job of turn `index`, e.g. one that timed out
+    /// before the conversation was saved, and update the turn with the
+    /// job's latest state.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BankrError::Config`] if there is no such turn, and
+    /// otherwise the same errors as [`send_with`](Self::send_with).
+    pub async fn resume_turn_with<A: BankrApi + ?Sized>(
+        &mut self,
+        api: &A,
+        index: usize,
+        poll: &PollConfig,
+    ) -> Result<&Turn, BankrError> {
+        let turn = self
+            .turns
+            .get_mut(index)
+            .ok_or_else(|| BankrError::Config(format!("conversation has no turn {index}")))?;
+        let mut handle =
+            JobHandle::new(api, &turn.job.job_id, turn.job.thread_id.clone(), poll.clone());
+        let result = handle.wait().await;
+        if let Some(job) = handle.last() {
+            turn.job = job.clone();
+        }
+        result?;
+        Ok(&self.turns[index])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{
+        mock::MockBankrApi,
+        types::{JobStatus, PromptResponse},
+    };
+
+    fn accepted(job_id: &str) -> PromptResponse {
+        PromptResponse {
+            success: true,
+            job_id: job_id.to_owned(),
+            thread_id: "thr_1".to_owned(),
+            status: "pending".to_owned(),
+            message: "Job submitted successfully".to_owned(),
+        }
+    }
+
+    fn completed(job_id: &str, prompt: &str, response: &str) -> JobResponse {
+        JobResponse {
+            thread_id: Some("thr_1".to_owned()),
+            prompt: prompt.to_owned(),
+            response: Some(response.to_owned()),
+            ..JobResponse::new(job_id, JobStatus::Completed)
+        }
+    }
+
+    #[tokio::test]
+    async fn follow_ups_reuse_thread_and_survive_serde() -> Result<(), BankrError> {
+        let mock = MockBankrApi::new();
+        let poll = PollConfig::fixed(Duration::ZERO, 3);
+        mock.push_submit_prompt(Ok(accepted("job_1")));
+        mock.push_get_job(Ok(completed("job_1", "price of ETH?", "$3,245")));
+        mock.push_submit_prompt(Ok(accepted("job_2")));
+        mock.push_get_job(Ok(completed("job_2", "and SOL?", "$145")));
+
+        let mut convo = Conversation::new();
+        convo.send_with(&mock, "price of ETH?", &poll).await?;
+        assert_eq!(convo.thread_id(), Some("thr_1"));
+
+        let json = serde_json::to_string(&convo)
+            .map_err(|e| BankrError::Deserialization(e.to_string()))?;
+        let mut convo: Conversation =
+            serde_json::from_str(&json).map_err(|e| BankrError::Deserialization(e.to_string()))?;
+        convo.send_with(&mock, "and SOL?", &poll).await?;
+
+        let requests = mock.prompt_requests();
+        assert_eq!(requests[0].thread_id, None);
+        assert_eq!(requests[1].thread_id.as_deref(), Some("thr_1"));
+        assert_eq!(convo.turns().len(), 2);
+        assert_eq!(convo.turns()[0].prompt, "price of ETH?");
+        assert_eq!(convo.last_response(), Some("$145"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn failed_and_unfinished_turns_are_recorded() -> Result<(), BankrError> {
+        let mock = MockBankrApi::new();
+        let poll = PollConfig::fixed(Duration::ZERO, 1);
+        mock.push_submit_prompt(Ok(accepted("job_1")));
+        mock.push_get_job(Err(BankrError::Transport("connection reset".to_owned())));
+        mock.push_submit_prompt(Ok(accepted("job_2")));
+        mock.push_get_job(Ok(JobResponse {
+            thread_id: Some("thr_1".to_owned()),
+            ..JobResponse::new("job_2", JobStatus::Processing)
+        }));
+        mock.push_submit_prompt(Ok(accepted("job_3")));
+        mock.push_get_job(Ok(JobResponse {
+            error: Some("insufficient balance".to_owned()),
+            ..JobResponse::new("job_3", JobStatus::Failed)
+        }));
+
+        let mut convo = Conversation::new();
+        let result = convo.send_with(&mock, "swap", &poll).await;
+        assert!(matches!(result, Err(BankrError::Transport(_))));
+        assert_eq!(convo.thread_id(), Some("thr_1"));
+        let result = convo.send_with(&mock, "bridge", &poll).await;
+        assert!(matches!(result, Err(BankrError::PollTimeout { .. })));
+        let result = convo.send_with(&mock, "swap again", &poll).await;
+        assert!(matches!(result, Err(BankrError::JobFailed { .. })));
+
+        let turns = convo.turns();
+        assert_eq!(turns.len(), 3);
+        assert_eq!(
+            (turns[0].job.job_id.as_str(), &turns[0].job.status),
+            ("job_1", &JobStatus::Pending)
+        );
+        assert_eq!(turns[0].job.thread_id.as_deref(), Some("thr_1"));
+        assert_eq!(turns[1].job.status, JobStatus::Processing);
+        assert_eq!(turns[2].job.status, JobStatus::Failed);
+        assert!(!turns[1].is_finished() && turns[2].is_finished());
+
+        // An unfinished turn survives a restart and can be resumed.
+        let json = serde_json::to_string(&convo)
+            .map_err(|e| BankrError::Deserialization(e.to_string()))?;
+        let mut convo: Conversation =
+            serde_json::from_str(&json).map_err(|e| BankrError::Deserialization(e.to_string()))?;
+        mock.push_get_job(Ok(completed("job_1", "swap", "Swapped")));
+        let turn = convo.resume_turn_with(&mock, 0, &poll).await?;
+        assert!(turn.is_finished());
+        assert_eq!(turn.job.response.as_deref(), Some("Swapped"));
+        assert!(matches!(
+            convo.resume_turn_with(&mock, 3, &poll).await,
+            Err(BankrError::Config(_))
+        ));
+        Ok(())
+    }
+}
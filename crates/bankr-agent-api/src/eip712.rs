@@ -0,0 +1,485 @@
+//! EIP-712 hashing and validation for [`TypedData`].
+//!
+//! [`TypedData::digest`] computes the hash a wallet signs for
+//! `eth_signTypedData_v4`, so a signature returned by `/agent/sign` can be
+//! checked against the data that was sent. [`TypedData::validate`] runs the
+//! same encoding and reports unknown types, missing fields and values that do
+//! not match their declared type.
+//!
+//! If `types` has no `EIP712Domain` entry, it is derived from the domain
+//! fields that are set, in the standard order (`name`, `version`, `chainId`,
+//! `verifyingContract`, `salt`).
+//!
+//! ```rust
+//! use bankr_agent_api::types::TypedData;
+//!
+//! let data: TypedData = serde_json::from_value(serde_json::json!({
+//!     "domain": { "name": "MyApp", "version": "1", "chainId": 8453 },
+//!     "types": { "Mail": [{ "name": "contents", "type": "string" }] },
+//!     "primaryType": "Mail",
+//!     "message": { "contents": "hello" }
+//! }))?;
+//! let digest = data.digest()?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde_json::Value;
+
+use crate::{
+    primitives::{Address, Bytes, U256, keccak256},
+    types::{TypedData, TypedDataField},
+};
+
+/// Name of the domain struct type.
+const DOMAIN_TYPE: &str = "EIP712Domain";
+
+/// Domain fields in the order used when `EIP712Domain` is not declared.
+const DOMAIN_FIELDS: [(&str, &str); 5] = [
+    ("name", "string"),
+    ("version", "string"),
+    ("chainId", "uint256"),
+    ("verifyingContract", "address"),
+    ("salt", "bytes32"),
+];
+
+/// Why typed data could not be encoded.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum Eip712Error {
+    /// A type is referenced but neither atomic nor defined in `types`.
+    #[error("unknown type `{0}`")]
+    UnknownType(String),
+    /// A field declared by a struct type has no value.
+    #[error("missing value for `{0}`")]
+    MissingField(String),
+    /// A domain field is set but not declared in `EIP712Domain`.
+    #[error("domain field `{0}` is not declared in EIP712Domain")]
+    UndeclaredDomainField(String),
+    /// A value does not match its declared type.
+    #[error("invalid value for `{path}` of type `{kind}`: {reason}")]
+    InvalidValue {
+        /// Dotted path of the value, e.g. `Mail.from.wallet`.
+        path: String,
+        /// Declared type.
+        kind: String,
+        /// What is wrong with the value.
+        reason: String,
+    },
+}
+
+impl TypedData {
+    /// Check that every type is known and the domain and message match their
+    /// types.
+    pub fn validate(&self) -> Result<(), Eip712Error> {
+        let types = self.resolved_types()?;
+        for fields in types.values() {
+            for field in fields {
+                check_type(&types, &field.kind)?;
+            }
+        }
+        self.digest().map(drop)
+    }
+
+    /// The domain separator: `hashStruct(EIP712Domain, domain)`.
+    pub fn domain_separator(&self) -> Result<[u8; 32], Eip712Error> {
+        let types = self.resolved_types()?;
+        let domain = serde_json::to_value(&self.domain).unwrap_or(Value::Null);
+        if let Some(fields) = types.get(DOMAIN_TYPE) {
+            let declared: BTreeSet<_> = fields.iter().map(|f| f.name.as_str()).collect();
+            if let Some(extra) = domain
+                .as_object()
+                .into_iter()
+                .flatten()
+                .map(|(k, _)| k)
+                .find(|k| !declared.contains(k.as_str()))
+            {
+                return Err(Eip712Error::UndeclaredDomainField(extra.clone()));
+            }
+        }
+        hash_struct(&types, DOMAIN_TYPE, &domain, DOMAIN_TYPE)
+    }
+
+    /// The struct hash of `message`: `hashStruct(primaryType, message)`.
+    pub fn struct_hash(&self) -> Result<[u8; 32], Eip712Error> {
+        let types = self.resolved_types()?;
+        hash_struct(&types, &self.primary_type, &self.message, &self.primary_type)
+    }
+
+    /// The EIP-712 digest:
+    /// `keccak256(0x1901 ‖ domainSeparator ‖ hashStruct(message))`.
+    pub fn digest(&self) -> Result<[u8; 32], Eip712Error> {
+        let mut buf = Vec::with_capacity(66);
+        buf.extend_from_slice(&[0x19, 0x01]);
+        buf.extend_from_slice(&self.domain_separator()?);
+        if self.primary_type != DOMAIN_TYPE {
+            buf.extend_from_slice(&self.struct_hash()?);
+        }
+        Ok(keccak256(&buf))
+    }
+
+    /// `types`, with `EIP712Domain` derived from the domain if absent.
+    fn resolved_types(&self) -> Result<Types, Eip712Error> {
+        let mut types = self.types.clone();
+        if !types.contains_key(DOMAIN_TYPE) {
+            let domain = serde_json::to_value(&self.domain).unwrap_or(Value::Null);
+            if let Some((extra, _)) = domain
+                .as_object()
+                .into_iter()
+                .flatten()
+                .find(|(k, _)| !DOMAIN_FIELDS.iter().any(|(name, _)| name == k))
+            {
+                return Err(Eip712Error::UndeclaredDomainField(extra.clone()));
+            }
+            let fields = DOMAIN_FIELDS
+                .iter()
+                .filter(|(name, _)| domain.get(name).is_some())
+                .map(|(name, kind)| TypedDataField {
+                    name: (*name).to_owned(),
+                    kind: (*kind).to_owned(),
+                })
+                .collect();
+            types.insert(DOMAIN_TYPE.to_owned(), fields);
+        }
+        if !types.contains_key(&self.primary_type) {
+            return Err(Eip712Error::UnknownType(self.primary_type.clone()));
+        }
+        Ok(types)
+    }
+}
+
+type Types = BTreeMap<String, Vec<TypedDataField>>;
+
+/// Split `T[]` / `T[n]` into `T` and the optional fixed length.
+fn array_element(kind: &str) -> Option<(&str, Option<&str>)> {
+    let inner = kind.strip_suffix(']')?;
+    let open = inner.rfind('[')?;
+    let len = &inner[open + 1..];
+    Some((&inner[..open], (!len.is_empty()).then_some(len)))
+}
+
+/// Whether `kind` is an atomic or dynamic Solidity type.
+fn is_atomic(kind: &str) -> bool {
+    let sized = |prefix: &str, step: u32, max: u32| {
+        kind.strip_prefix(prefix)
+            .and_then(|n| n.parse::<u32>().ok())
+            .is_some_and(|n| n > 0 && n <= max && n % step == 0)
+    };
+    matches!(kind, "address" | "bool" | "string" | "bytes") ||
+        sized("bytes", 1, 32) ||
+        sized("uint", 8, 256) ||
+        sized("int", 8, 256)
+}
+
+fn check_type(types: &Types, kind: &str) -> Result<(), Eip712Error> {
+    if let Some((element, _)) = array_element(kind) {
+        return check_type(types, element);
+    }
+    if is_atomic(kind) || types.contains_key(kind) {
+        Ok(())
+    } else {
+        Err(Eip712Error::UnknownType(kind.to_owned()))
+    }
+}
+
+/// `encodeType`: the primary type followed by its dependencies sorted by name.
+fn encode_type(types: &Types, primary: &str) -> Result<String, Eip712Error> {
+    let mut deps = BTreeSet::new();
+    collect_deps(types, primary, &mut deps)?;
+    deps.remove(primary);
+
+    let mut out = String::new();
+    for name in std::iter::once(primary).chain(deps.iter().map(String::as_str)) {
+        let fields = types.get(name).ok_or_else(|| Eip712Error::UnknownType(name.to_owned()))?;
+        let fields: Vec<_> = fields.iter().map(|f| format!("{} {}", f.kind, f.name)).collect();
+        out.push_str(&format!("{name}({})", fields.join(",")));
+    }
+    Ok(out)
+}
+
+fn collect_deps(types: &Types, kind: &str, deps: &mut BTreeSet<String>) -> Result<(), Eip712Error> {
+    let base = base_type(kind);
+    if deps.contains(base) || is_atomic(base) {
+        return Ok(());
+    }
+    let fields = types.get(base).ok_or_else(|| Eip712Error::UnknownType(base.to_owned()))?;
+    deps.insert(base.to_owned());
+    for field in fields {
+        collect_deps(types, &field.kind, deps)?;
+    }
+    Ok(())
+}
+
+/// `kind` with all array suffixes removed.
+fn base_type(mut kind: &str) -> &str {
+    while let Some((element, _)) = array_element(kind) {
+        kind = element;
+    }
+    kind
+}
+
+/// `hashStruct(s) = keccak256(typeHash ‖ encodeData(s))`.
+fn hash_struct(
+    types: &Types,
+    kind: &str,
+    value: &Value,
+    path: &str,
+) -> Result<[u8; 32], Eip712Error> {
+    let fields = types.get(kind).ok_or_else(|| Eip712Error::UnknownType(kind.to_owned()))?;
+    let object = value.as_object().ok_or_else(|| invalid(path, kind, "expected an object"))?;
+
+    let mut buf = Vec::with_capacity(32 * (fields.len() + 1));
+    buf.extend_from_slice(&keccak256(encode_type(types, kind)?.as_bytes()));
+    for field in fields {
+        let path = format!("{path}.{}", field.name);
+        let value =
+            object.get(&field.name).ok_or_else(|| Eip712Error::MissingField(path.clone()))?;
+        buf.extend_from_slice(&encode_value(types, &field.kind, value, &path)?);
+    }
+    Ok(keccak256(&buf))
+}
+
+/// Encode one field value as a 32-byte word.
+fn encode_value(
+    types: &Types,
+    kind: &str,
+    value: &Value,
+    path: &str,
+) -> Result<[u8; 32], Eip712Error> {
+    if let Some((element, len)) = array_element(kind) {
+        let items = value.as_array().ok_or_else(|| invalid(path, kind, "expected an array"))?;
+        if let Some(len) = len &&
+            len.parse::<usize>().ok() != Some(items.len())
+        {
+            return Err(invalid(
+                path,
+                kind,
+                &format!("expected {len} elements, got {}", items.len()),
+            ));
+        }
+        let mut buf = Vec::with_capacity(32 * items.len());
+        for (i, item) in items.iter().enumerate() {
+            buf.extend_from_slice(&encode_value(types, element, item, &format!("{path}[{i}]"))?);
+        }
+        return Ok(keccak256(&buf));
+    }
+    if types.contains_key(kind) {
+        return hash_struct(types, kind, value, path);
+    }
+
+    let mut word = [0u8; 32];
+    match kind {
+        "string" => {
+            let s = value.as_str().ok_or_else(|| invalid(path, kind, "expected a string"))?;
+            word = keccak256(s.as_bytes());
+        }
+        "bytes" => word = keccak256(bytes(value, path, kind)?.as_slice()),
+        "bool" => {
+            let b = value.as_bool().ok_or_else(|| invalid(path, kind, "expected true or false"))?;
+            word[31] = u8::from(b);
+        }
+        "address" => {
+            let address: Address = value
+                .as_str()
+                .ok_or_else(|| invalid(path, kind, "expected a string"))?
+                .parse()
+                .map_err(|e| invalid(path, kind, &format!("{e}")))?;
+            word[12..].copy_from_slice(address.as_bytes());
+        }
+        _ => {
+            if let Some(size) = kind.strip_prefix("bytes").and_then(|n| n.parse::<usize>().ok()) {
+                let data = bytes(value, path, kind)?;
+                if data.len() != size {
+                    return Err(invalid(
+                        path,
+                        kind,
+                        &format!("expected {size} bytes, got {}", data.len()),
+                    ));
+                }
+                word[..size].copy_from_slice(data.as_slice());
+            } else if let Some(bits) = kind.strip_prefix("uint").and_then(|n| n.parse::<u32>().ok())
+            {
+                let (negative, magnitude) = integer(value, path, kind)?;
+                if negative || magnitude.bits() > bits {
+                    return Err(invalid(path, kind, "out of range"));
+                }
+                word = magnitude.to_be_bytes();
+            } else if let Some(bits) = kind.strip_prefix("int").and_then(|n| n.parse::<u32>().ok())
+            {
+                let (negative, magnitude) = integer(value, path, kind)?;
+                // The most negative value, -2^(bits-1), has one more bit of
+                // magnitude than the largest positive one.
+                let in_range = magnitude.bits() < bits || (negative && magnitude == pow2(bits - 1));
+                if !in_range {
+                    return Err(invalid(path, kind, "out of range"));
+                }
+                let encoded = if negative { magnitude.wrapping_neg() } else { magnitude };
+                word = encoded.to_be_bytes();
+            } else {
+                return Err(Eip712Error::UnknownType(kind.to_owned()));
+            }
+        }
+    }
+    Ok(word)
+}
+
+/// `2^bit`, for `bit < 256`.
+fn pow2(bit: u32) -> U256 {
+    let mut be = [0u8; 32];
+    be[31 - (bit / 8) as usize] = 1 << (bit % 8);
+    U256::from_be_slice(&be).unwrap_or(U256::ZERO)
+}
+
+/// Parse an integer given as a JSON number or a decimal/hex string.
+fn integer(value: &Value, path: &str, kind: &str) -> Result<(bool, U256), Eip712Error> {
+    let text = match value {
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.clone(),
+        _ => return Err(invalid(path, kind, "expected a number or numeric string")),
+    };
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.as_str()),
+    };
+    let magnitude: U256 = digits.parse().map_err(|e| invalid(path, kind, &format!("{e}")))?;
+    Ok((negative && !magnitude.is_zero(), magnitude))
+}
+
+fn bytes(value: &Value, path: &str, kind: &str) -> Result<Bytes, Eip712Error> {
+    value
+        .as_str()
+        .ok_or_else(|| invalid(path, kind, "expected a 0x hex string"))?
+        .parse()
+        .map_err(|e| invalid(path, kind, &format!("{e}")))
+}
+
+fn invalid(path: &str, kind: &str, reason: &str) -> Eip712Error {
+    Eip712Error::InvalidValue {
+        path: path.to_owned(),
+        kind: kind.to_owned(),
+        reason: reason.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::encode_hex;
+
+    /// The `Mail` example from the EIP-712 specification.
+    fn mail() -> Result<TypedData, serde_json::Error> {
+        serde_json::from_value(serde_json::json!({
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "version", "type": "string" },
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" }
+                ],
+                "Person": [
+                    { "name": "name", "type": "string" },
+                    { "name": "wallet", "type": "address" }
+                ],
+                "Mail": [
+                    { "name": "from", "type": "Person" },
+                    { "name": "to", "type": "Person" },
+                    { "name": "contents", "type": "string" }
+                ]
+            },
+            "primaryType": "Mail",
+            "domain": {
+                "name": "Ether Mail",
+                "version": "1",
+                "chainId": 1,
+                "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+            },
+            "message": {
+                "from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+                "to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+                "contents": "Hello, Bob!"
+            }
+        }))
+    }
+
+    #[test]
+    fn spec_mail_example() -> Result<(), Box<dyn std::error::Error>> {
+        let data = mail()?;
+        data.validate()?;
+        assert_eq!(
+            encode_type(&data.resolved_types()?, "Mail")?,
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+        );
+        assert_eq!(
+            encode_hex(&data.domain_separator()?),
+            "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+        );
+        assert_eq!(
+            encode_hex(&data.struct_hash()?),
+            "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+        );
+        assert_eq!(
+            encode_hex(&data.digest()?),
+            "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+
+        // Omitting EIP712Domain derives the same domain type.
+        let mut implicit = data;
+        implicit.types.remove(DOMAIN_TYPE);
+        assert_eq!(
+            encode_hex(&implicit.digest()?),
+            "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_malformed_data() -> Result<(), serde_json::Error> {
+        let mut unknown = mail()?;
+        unknown.types.insert(
+            "Extra".to_owned(),
+            vec![TypedDataField { name: "x".to_owned(), kind: "Missing".to_owned() }],
+        );
+        assert_eq!(unknown.validate(), Err(Eip712Error::UnknownType("Missing".to_owned())));
+
+        let mut missing = mail()?;
+        missing.message["from"].as_object_mut().map(|o| o.remove("wallet"));
+        assert_eq!(
+            missing.validate(),
+            Err(Eip712Error::MissingField("Mail.from.wallet".to_owned()))
+        );
+
+        let mut wrong = mail()?;
+        wrong.message["to"]["wallet"] = "0x1234".into();
+        assert!(matches!(
+            wrong.validate(),
+            Err(Eip712Error::InvalidValue { ref path, ref kind, .. }) if path == "Mail.to.wallet" && kind == "address"
+        ));
+
+        let mut undeclared = mail()?;
+        undeclared.domain.salt = Some(format!("0x{}", "00".repeat(32)));
+        assert_eq!(
+            undeclared.validate(),
+            Err(Eip712Error::UndeclaredDomainField("salt".to_owned()))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn integer_ranges() -> Result<(), Eip712Error> {
+        let types = Types::new();
+        let word = |kind: &str, value: Value| encode_value(&types, kind, &value, "v");
+
+        assert_eq!(word("uint8", 255.into())?[31], 0xff);
+        assert!(word("uint8", 256.into()).is_err());
+        assert!(word("uint256", "-1".into()).is_err());
+        let min = word("int8", (-128).into())?;
+        assert!(min[..31].iter().all(|b| *b == 0xff));
+        assert_eq!(min[31], 0x80);
+        assert!(word("int8", 128.into()).is_err());
+        assert!(word("int8", (-129).into()).is_err());
+        assert_eq!(word("int256", "-1".into())?, [0xff; 32]);
+        assert_eq!(word("bytes4", "0xdeadbeef".into())?[..4], [0xde, 0xad, 0xbe, 0xef]);
+        assert!(word("bytes4", "0xdead".into()).is_err());
+        Ok(())
+    }
+}
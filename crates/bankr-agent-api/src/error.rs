@@ -1,6 +1,12 @@
 //! Error types for the `bankr-agent-api` crate.
 
-use crate::types::ApiErrorBody;
+use hpx_transport::TransportError;
+
+use crate::{
+    eip712::Eip712Error,
+    primitives::PrimitiveError,
+    types::{ApiErrorBody, JobResponse},
+};
 
 /// Errors that can occur when interacting with the Bankr Agent API.
 #[derive(Debug, thiserror::Error)]
@@ -9,6 +15,67 @@ pub enum BankrError {
     #[error("HTTP transport error: {0}")]
     Transport(String),
 
+    /// The connection to the API could not be established, so the request
+    /// was never sent.
+    #[error("Connection error: {0}")]
+    Connect(String),
+
+    /// The API key is missing, invalid, or revoked (HTTP 401).
+    #[error("Unauthorized: {body}")]
+    Unauthorized {
+        /// Parsed error body.
+        body: ApiErrorBody,
+    },
+
+    /// The API key does not have Agent API access enabled (HTTP 403).
+    #[error("Agent API access not enabled: {body}")]
+    AgentAccessDisabled {
+        /// Parsed error body.
+        body: ApiErrorBody,
+    },
+
+    /// The API key is read-only and cannot sign or submit (HTTP 403).
+    #[error("Read-only API key: {body}")]
+    ReadOnlyKey {
+        /// Parsed error body.
+        body: ApiErrorBody,
+    },
+
+    /// The request came from an IP outside the key's allowlist (HTTP 403).
+    #[error("IP address not allowed: {body}")]
+    IpNotAllowed {
+        /// Parsed error body.
+        body: ApiErrorBody,
+    },
+
+    /// The requested resource (e.g. a job) does not exist (HTTP 404).
+    #[error("Not found: {body}")]
+    NotFound {
+        /// Parsed error body.
+        body: ApiErrorBody,
+    },
+
+    /// The job has already completed or failed and cannot be cancelled
+    /// (HTTP 400).
+    #[error("Job cannot be cancelled: {body}")]
+    JobNotCancellable {
+        /// Parsed error body.
+        body: ApiErrorBody,
+    },
+
+    /// A rate limit was hit (HTTP 429).
+    #[error("Rate limited: {body}")]
+    RateLimited {
+        /// When the rate-limit counter resets (Unix ms), if reported.
+        reset_at: Option<u64>,
+        /// Rate-limit quota, if reported.
+        limit: Option<u64>,
+        /// Number of messages used in the current window, if reported.
+        used: Option<u64>,
+        /// Parsed error body.
+        body: ApiErrorBody,
+    },
+
     /// The API returned a non-success HTTP status code.
     #[error("API error (HTTP {status}): {body}")]
     Api {
@@ -22,11 +89,18 @@ pub enum BankrError {
     #[error("Deserialization error: {0}")]
     Deserialization(String),
 
-    /// Job polling timed out.
+    /// Job polling timed out before the job reached a terminal state.
+    ///
+    /// More fields may be added; match with `..` and read the last job
+    /// through [`BankrError::last_job`].
     #[error("Job polling timed out after {attempts} attempts")]
+    #[non_exhaustive]
     PollTimeout {
         /// Number of poll attempts made.
         attempts: u32,
+        /// The last job state observed, if any poll succeeded. Its `job_id`
+        /// and `thread_id` can be used to resume waiting later.
+        last_job: Option<Box<JobResponse>>,
     },
 
     /// Job failed on the server side.
@@ -40,11 +114,124 @@ pub enum BankrError {
     #[error("Job was cancelled")]
     JobCancelled,
 
+    /// Waiting for a job was interrupted by the caller's cancellation
+    /// signal.
+    #[error("Interrupted while waiting for job {job_id}: {outcome}")]
+    Interrupted {
+        /// The job that was being waited on.
+        job_id: String,
+        /// What happened to the server-side job.
+        outcome: CancelOutcome,
+    },
+
+    /// A request field is not a valid EVM address, amount or hex string.
+    /// Detected before sending the request.
+    #[error("Invalid request: {0}")]
+    InvalidInput(#[from] PrimitiveError),
+
+    /// EIP-712 typed data failed local validation. Detected before sending
+    /// the request.
+    #[error("Invalid typed data: {0}")]
+    InvalidTypedData(#[from] Eip712Error),
+
     /// Client configuration error.
     #[error("Configuration error: {0}")]
     Config(String),
 }
 
+/// What happened to a server-side job after its wait was interrupted.
+#[derive(Debug)]
+pub enum CancelOutcome {
+    /// The job was still cancellable and has been cancelled.
+    Cancelled,
+    /// The job had already reached a terminal state and could not be
+    /// cancelled. Its final state is included: a completed job's actions
+    /// (e.g. a swap) have taken effect.
+    AlreadyFinished(Box<JobResponse>),
+    /// The cancel request failed; the job may still be running.
+    CancelFailed(Box<BankrError>),
+}
+
+impl std::fmt::Display for CancelOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cancelled => write!(f, "job cancelled"),
+            Self::AlreadyFinished(job) => write!(f, "job already {}", job.status),
+            Self::CancelFailed(err) => write!(f, "cancel failed ({err}), job may still be running"),
+        }
+    }
+}
+
+impl BankrError {
+    /// Build the most specific error variant for a non-2xx response.
+    ///
+    /// The body is parsed as the standard Bankr error envelope. If it is not
+    /// valid JSON, the raw text is kept as the error message.
+    pub fn from_response(status: u16, body: &str) -> Self {
+        let body: ApiErrorBody = serde_json::from_str(body).unwrap_or_else(|_| ApiErrorBody {
+            message: (!body.trim().is_empty()).then(|| body.trim().to_owned()),
+            ..ApiErrorBody::default()
+        });
+
+        match (status, body.error.as_deref()) {
+            (401, _) => Self::Unauthorized { body },
+            (403, Some("Agent API access not enabled")) => Self::AgentAccessDisabled { body },
+            (403, Some("Read-only API key")) => Self::ReadOnlyKey { body },
+            (403, Some("IP address not allowed")) => Self::IpNotAllowed { body },
+            (404, _) => Self::NotFound { body },
+            (400, Some("Job already completed" | "Job already failed")) => {
+                Self::JobNotCancellable { body }
+            }
+            (429, _) => Self::RateLimited {
+                reset_at: body.reset_at,
+                limit: body.limit,
+                used: body.used,
+                body,
+            },
+            _ => Self::Api { status, body },
+        }
+    }
+
+    /// The last observed job state of a [`BankrError::PollTimeout`].
+    pub fn last_job(&self) -> Option<&JobResponse> {
+        match self {
+            Self::PollTimeout { last_job, .. } => last_job.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// HTTP status code of the response that produced this error, if any.
+    pub const fn status(&self) -> Option<u16> {
+        match self {
+            Self::Unauthorized { .. } => Some(401),
+            Self::AgentAccessDisabled { .. } |
+            Self::ReadOnlyKey { .. } |
+            Self::IpNotAllowed { .. } => Some(403),
+            Self::NotFound { .. } => Some(404),
+            Self::JobNotCancellable { .. } => Some(400),
+            Self::RateLimited { .. } => Some(429),
+            Self::Api { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+}
+
+impl From<TransportError> for BankrError {
+    fn from(err: TransportError) -> Self {
+        match err {
+            TransportError::Api { status, body } => Self::from_response(status.as_u16(), &body),
+            TransportError::Http(e) if e.is_connect() => Self::Connect(e.to_string()),
+            // Problems building the request are not transient.
+            TransportError::Http(e) if e.is_builder() => Self::Config(e.to_string()),
+            err @ (TransportError::Config { .. } | TransportError::Auth { .. }) => {
+                Self::Config(err.to_string())
+            }
+            TransportError::Serialization(e) => Self::Deserialization(e.to_string()),
+            other => Self::Transport(other.to_string()),
+        }
+    }
+}
+
 impl std::fmt::Display for ApiErrorBody {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if let Some(ref msg) = self.message {
@@ -56,3 +243,99 @@ impl std::fmt::Display for ApiErrorBody {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn unauthorized() {
+        let err = BankrError::from_response(
+            401,
+            r#"{"error":"Authentication required","message":"Please provide a valid API key"}"#,
+        );
+        assert!(matches!(err, BankrError::Unauthorized { .. }));
+        assert_eq!(err.status(), Some(401));
+    }
+
+    #[test]
+    fn forbidden_variants() {
+        let err = BankrError::from_response(
+            403,
+            r#"{"error":"Agent API access not enabled","message":"Enable agent access"}"#,
+        );
+        assert!(matches!(err, BankrError::AgentAccessDisabled { .. }));
+
+        let err = BankrError::from_response(
+            403,
+            r#"{"error":"Read-only API key","message":"This API key has read-only access"}"#,
+        );
+        assert!(matches!(err, BankrError::ReadOnlyKey { .. }));
+
+        let err = BankrError::from_response(
+            403,
+            r#"{"error":"IP address not allowed","message":"IP address not allowed for this API key"}"#,
+        );
+        assert!(matches!(err, BankrError::IpNotAllowed { .. }));
+
+        let err = BankrError::from_response(403, r#"{"error":"Something else"}"#);
+        assert!(matches!(err, BankrError::Api { status: 403, .. }));
+    }
+
+    #[test]
+    fn job_not_found_and_not_cancellable() {
+        let err = BankrError::from_response(
+            404,
+            r#"{"error":"Job not found","message":"No job found with ID abc123"}"#,
+        );
+        assert!(matches!(err, BankrError::NotFound { .. }));
+
+        let err = BankrError::from_response(
+            400,
+            r#"{"success":false,"error":"Job already completed","message":"Cannot cancel a completed job"}"#,
+        );
+        assert!(matches!(err, BankrError::JobNotCancellable { .. }));
+
+        let err = BankrError::from_response(
+            400,
+            r#"{"success":false,"error":"Job already failed","message":"Cannot cancel a failed job"}"#,
+        );
+        assert!(matches!(err, BankrError::JobNotCancellable { .. }));
+    }
+
+    #[test]
+    fn rate_limited_carries_quota() {
+        let err = BankrError::from_response(
+            429,
+            r#"{"error":"Daily limit exceeded","message":"You have reached your daily API limit","resetAt":1736942400000,"limit":100,"used":100}"#,
+        );
+        assert!(matches!(
+            err,
+            BankrError::RateLimited {
+                reset_at: Some(1_736_942_400_000),
+                limit: Some(100),
+                used: Some(100),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn non_json_body_is_kept_as_message() {
+        let err = BankrError::from_response(502, "Bad Gateway");
+        assert_eq!(err.to_string(), "API error (HTTP 502): Bad Gateway");
+
+        let err = BankrError::from_response(500, "");
+        assert_eq!(err.to_string(), "API error (HTTP 500): (no details)");
+    }
+
+    #[test]
+    fn transport_configuration_errors_map_to_config() {
+        let err = BankrError::from(TransportError::Config { message: "bad URL".to_owned() });
+        assert!(matches!(err, BankrError::Config(_)));
+        let err = BankrError::from(TransportError::Timeout { duration: Duration::from_secs(1) });
+        assert!(matches!(err, BankrError::Transport(_)));
+    }
+}
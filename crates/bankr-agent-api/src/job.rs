@@ -0,0 +1,214 @@
+//! [`JobHandle`]: a submitted job bound to the client that created it.
+//!
+//! ```rust,no_run
+//! use bankr_agent_api::{BankrAgentClient, types::PromptRequest};
+//!
+//! # async fn example(client: &BankrAgentClient) -> Result<(), bankr_agent_api::error::BankrError> {
+//! let req = PromptRequest { prompt: "swap 10 USDC to ETH on base".to_owned(), thread_id: None };
+//! let mut job = client.start_prompt(&req).await?;
+//! println!("job {} in thread {:?}", job.job_id(), job.thread_id());
+//!
+//! let done = job.wait().await?;
+//! println!("{:?}", done.response);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    BankrAgentClient,
+    api::{self, BankrApi},
+    error::BankrError,
+    poll::PollConfig,
+    types::{CancelJobResponse, JobResponse, JobStatus, PromptRequest},
+};
+
+/// A handle to a server-side job.
+///
+/// Borrows the API client, remembers the job and thread IDs, and caches the
+/// last [`JobResponse`] fetched through it.
+#[derive(Debug)]
+pub struct JobHandle<'a, A: BankrApi + ?Sized = BankrAgentClient> {
+    api: &'a A,
+    job_id: String,
+    thread_id: Option<String>,
+    poll: PollConfig,
+    last: Option<JobResponse>,
+}
+
+impl<'a, A: BankrApi + ?Sized> JobHandle<'a, A> {
+    /// Attach to an existing job, e.g. one whose wait previously timed out.
+    pub fn new(api: &'a A, job_id: &str, thread_id: Option<String>, poll: PollConfig) -> Self {
+        Self { api, job_id: job_id.to_owned(), thread_id, poll, last: None }
+    }
+
+    /// Submit `req` and return a handle to the created job.
+    pub async fn submit(
+        api: &'a A,
+        req: &PromptRequest,
+        poll: PollConfig,
+    ) -> Result<Self, BankrError> {
+        let resp = api.submit_prompt(req).await?;
+        Ok(Self::new(api, &resp.job_id, Some(resp.thread_id), poll))
+    }
+
+    /// The job ID.
+    pub fn job_id(&self) -> &str {
+        &self.job_id
+    }
+
+    /// The conversation thread the job belongs to, if known.
+    pub fn thread_id(&self) -> Option<&str> {
+        self.thread_id.as_deref()
+    }
+
+    /// The last job state fetched through this handle, if any.
+    pub const fn last(&self) -> Option<&JobResponse> {
+        self.last.as_ref()
+    }
+
+    /// Fetch the current job state.
+    pub async fn refresh(&mut self) -> Result<&JobResponse, BankrError> {
+        let job = self.api.get_job(&self.job_id).await?;
+        Ok(self.remember(job))
+    }
+
+    /// Wait for the job to reach a terminal state using the handle's
+    /// [`PollConfig`].
+    pub async fn wait(&mut self) -> Result<JobResponse, BankrError> {
+        let config = self.poll.clone();
+        self.wait_with(&config).await
+    }
+
+    /// Wait for the job to reach a terminal state using `config`.
+    ///
+    /// On [`BankrError::PollTimeout`] the last observed state is kept, so
+    /// calling `wait` again resumes where this wait left off. A failed or
+    /// cancelled job is kept as well, before its error is returned.
+    pub async fn wait_with(&mut self, config: &PollConfig) -> Result<JobResponse, BankrError> {
+        match api::poll_until_terminal(self.api, &self.job_id, config).await {
+            Ok(job) => api::completed(self.remember(job).clone()),
+            Err(e) => {
+                if let Some(job) = e.last_job() {
+                    self.remember(job.clone());
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Cancel the job.
+    ///
+    /// On success the cached [`last`](Self::last) state takes the status and
+    /// cancellation time from the response.
+    pub async fn cancel(&mut self) -> Result<CancelJobResponse, BankrError> {
+        let resp = self.api.cancel_job(&self.job_id).await?;
+        if let Ok(status) = serde_json::from_value::<JobStatus>(resp.status.clone().into()) {
+            let mut job = self.last.take().unwrap_or_else(|| JobResponse {
+                thread_id: self.thread_id.clone(),
+                prompt: resp.prompt.clone().unwrap_or_default(),
+                created_at: resp.created_at.clone().unwrap_or_default(),
+                ..JobResponse::new(&self.job_id, status.clone())
+            });
+            job.status = status;
+            job.cancellable = Some(false);
+            job.cancelled_at.clone_from(&resp.cancelled_at);
+            self.remember(job);
+        }
+        Ok(resp)
+    }
+
+    fn remember(&mut self, job: JobResponse) -> &JobResponse {
+        if job.thread_id.is_some() {
+            self.thread_id.clone_from(&job.thread_id);
+        }
+        self.last.insert(job)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{
+        mock::{MockBankrApi, MockCall},
+        types::PromptResponse,
+    };
+
+    fn job(status: JobStatus) -> JobResponse {
+        JobResponse { thread_id: Some("thr_1".to_owned()), ..JobResponse::new("job_1", status) }
+    }
+
+    #[tokio::test]
+    async fn submit_refresh_and_wait() -> Result<(), BankrError> {
+        let mock = MockBankrApi::new();
+        mock.push_submit_prompt(Ok(PromptResponse {
+            success: true,
+            job_id: "job_1".to_owned(),
+            thread_id: "thr_1".to_owned(),
+            status: "pending".to_owned(),
+            message: "Job submitted successfully".to_owned(),
+        }));
+        mock.push_get_job(Ok(job(JobStatus::Processing)));
+        mock.push_get_job(Ok(job(JobStatus::Completed)));
+
+        let req = PromptRequest { prompt: "swap".to_owned(), thread_id: None };
+        let mut handle =
+            JobHandle::submit(&mock, &req, PollConfig::fixed(Duration::ZERO, 5)).await?;
+        assert_eq!(handle.job_id(), "job_1");
+        assert_eq!(handle.thread_id(), Some("thr_1"));
+        assert!(handle.last().is_none());
+
+        assert_eq!(handle.refresh().await?.status, JobStatus::Processing);
+        assert_eq!(handle.wait().await?.status, JobStatus::Completed);
+        assert_eq!(handle.last().map(|j| &j.status), Some(&JobStatus::Completed));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn timeout_keeps_last_state_and_cancel_uses_job_id() {
+        let mock = MockBankrApi::new();
+        mock.push_get_job(Ok(job(JobStatus::Pending)));
+
+        let mut handle = JobHandle::new(&mock, "job_1", None, PollConfig::default());
+        let err = handle.wait_with(&PollConfig::fixed(Duration::ZERO, 1)).await;
+        assert!(matches!(err, Err(BankrError::PollTimeout { .. })));
+        assert_eq!(handle.last().map(|j| &j.status), Some(&JobStatus::Pending));
+        assert_eq!(handle.thread_id(), Some("thr_1"));
+
+        let _ = handle.cancel().await;
+        assert!(matches!(mock.calls().last(), Some(MockCall::CancelJob(id)) if id == "job_1"));
+    }
+
+    #[tokio::test]
+    async fn cancel_updates_last_state() -> Result<(), BankrError> {
+        let cancelled = || CancelJobResponse {
+            success: true,
+            job_id: "job_1".to_owned(),
+            status: "cancelled".to_owned(),
+            prompt: Some("swap".to_owned()),
+            created_at: None,
+            cancelled_at: Some("2024-01-15T10:31:00Z".to_owned()),
+        };
+        let mock = MockBankrApi::new();
+        mock.push_get_job(Ok(job(JobStatus::Processing)));
+        mock.push_cancel_job(Ok(cancelled()));
+        mock.push_cancel_job(Ok(cancelled()));
+
+        let mut handle = JobHandle::new(&mock, "job_1", None, PollConfig::default());
+        handle.refresh().await?;
+        handle.cancel().await?;
+        let last = handle.last().map(|j| (&j.status, j.cancelled_at.as_deref()));
+        assert_eq!(last, Some((&JobStatus::Cancelled, Some("2024-01-15T10:31:00Z"))));
+        assert_eq!(handle.last().and_then(|j| j.thread_id.as_deref()), Some("thr_1"));
+
+        // Without a fetched state, one is built from the response.
+        let mut fresh = JobHandle::new(&mock, "job_1", None, PollConfig::default());
+        fresh.cancel().await?;
+        assert_eq!(
+            fresh.last().map(|j| (&j.status, j.prompt.as_str())),
+            Some((&JobStatus::Cancelled, "swap"))
+        );
+        Ok(())
+    }
+}
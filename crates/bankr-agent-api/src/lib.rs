@@ -22,9 +22,25 @@
 //! # }
 //! ```
 
+pub mod api;
+pub mod chain;
 pub mod client;
+pub mod conversation;
+pub mod eip712;
 pub mod error;
+pub mod job;
+pub mod llm;
+pub mod mock;
+pub mod poll;
+pub mod primitives;
+pub mod retry;
+mod rlp;
+pub mod transaction;
 pub mod types;
+pub mod units;
+pub mod verify;
+pub mod watch;
 
+pub use api::BankrApi;
 // Re-export the main client type at crate root for convenience.
-pub use client::BankrAgentClient;
+pub use client::{BankrAgentClient, BankrAgentClientBuilder};
@@ -0,0 +1,602 @@
+//! Client for the Bankr LLM Gateway at `llm.bankr.bot`.
+//!
+//! The gateway speaks the OpenAI chat completions protocol. It needs a key
+//! with `llmGatewayEnabled`; this can be the Agent API key or a separate
+//! LLM key (`BANKR_LLM_KEY`). Requests share the Agent API client's
+//! [`BankrError`] variants and [`RetryPolicy`].
+//!
+//! ```rust,no_run
+//! use bankr_agent_api::llm::{ChatCompletionRequest, ChatMessage, LlmClient};
+//! use futures_util::StreamExt;
+//!
+//! # async fn example() -> Result<(), bankr_agent_api::error::BankrError> {
+//! let llm = LlmClient::new("your_llm_key")?;
+//! let req = ChatCompletionRequest::new("claude-sonnet-4.5", vec![ChatMessage::user("gm")]);
+//!
+//! let completion = llm.chat_completion(&req).await?;
+//! println!("{}", completion.text().unwrap_or_default());
+//!
+//! let mut stream = std::pin::pin!(llm.chat_completion_stream(&req).await?);
+//! while let Some(chunk) = stream.next().await {
+//!     print!("{}", chunk?.text());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::time::Duration;
+
+use futures_util::{Stream, stream};
+use hpx_transport::{
+    Authentication, ExchangeClient, TransportError,
+    auth::{ApiKeyAuth, BearerAuth, CompositeAuth},
+    exchange::{RestClient, RestConfig},
+};
+use http::{
+    HeaderMap, HeaderValue, Method,
+    header::{ACCEPT, CONTENT_TYPE},
+};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+use tracing::debug;
+
+use crate::{
+    client::USER_AGENT,
+    error::BankrError,
+    retry::{self, Idempotency, RetryPolicy},
+};
+
+/// Default base URL for the LLM Gateway.
+pub const DEFAULT_BASE_URL: &str = "https://llm.bankr.bot";
+
+/// Default request timeout. Also bounds how long a streamed completion may
+/// run.
+const DEFAULT_TIMEOUT: Duration = Duration::from_mins(5);
+
+const CHAT_COMPLETIONS: &str = "/v1/chat/completions";
+
+/// The key is sent both as an OpenAI-style bearer token and as `X-API-Key`.
+type LlmAuth = CompositeAuth<BearerAuth, ApiKeyAuth>;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Author of a chat message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    /// Instructions for the model.
+    System,
+    /// The user.
+    User,
+    /// The model.
+    Assistant,
+    /// Output of a tool call.
+    Tool,
+}
+
+/// One message of a chat conversation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatMessage {
+    /// Who wrote the message.
+    pub role: Role,
+    /// Message text (empty for assistant messages that only call tools).
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub content: String,
+    /// Fields not modelled above (e.g. `tool_calls`, `name`).
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl ChatMessage {
+    /// A message with the given role and text.
+    pub fn new(role: Role, content: impl Into<String>) -> Self {
+        Self { role, content: content.into(), extra: Map::new() }
+    }
+
+    /// A system message.
+    pub fn system(content: impl Into<String>) -> Self {
+        Self::new(Role::System, content)
+    }
+
+    /// A user message.
+    pub fn user(content: impl Into<String>) -> Self {
+        Self::new(Role::User, content)
+    }
+
+    /// An assistant message.
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self::new(Role::Assistant, content)
+    }
+}
+
+/// Request body for `POST /v1/chat/completions`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatCompletionRequest {
+    /// Model ID, as listed by [`LlmClient::list_models`].
+    pub model: String,
+    /// The conversation so far.
+    pub messages: Vec<ChatMessage>,
+    /// Upper bound on generated tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    /// Sampling temperature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    /// Other OpenAI-compatible parameters (`top_p`, `stop`, `tools`, …).
+    /// `stream` is set by the client.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl ChatCompletionRequest {
+    /// A request for `model` with `messages` and default parameters.
+    pub fn new(model: impl Into<String>, messages: Vec<ChatMessage>) -> Self {
+        Self {
+            model: model.into(),
+            messages,
+            max_tokens: None,
+            temperature: None,
+            extra: Map::new(),
+        }
+    }
+}
+
+/// Token usage reported for a completion.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Usage {
+    /// Tokens in the prompt.
+    #[serde(default)]
+    pub prompt_tokens: u64,
+    /// Tokens generated.
+    #[serde(default)]
+    pub completion_tokens: u64,
+    /// Sum of the above.
+    #[serde(default)]
+    pub total_tokens: u64,
+    /// Provider-specific fields (e.g. cost, cached tokens).
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// One alternative of a [`ChatCompletion`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatChoice {
+    /// Position in `choices`.
+    #[serde(default)]
+    pub index: u32,
+    /// The generated message.
+    pub message: ChatMessage,
+    /// Why generation stopped (`stop`, `length`, `tool_calls`, …).
+    pub finish_reason: Option<String>,
+}
+
+/// Response from `POST /v1/chat/completions`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatCompletion {
+    /// Completion ID.
+    pub id: String,
+    /// Model that produced the completion.
+    pub model: String,
+    /// Creation time (Unix seconds).
+    pub created: Option<u64>,
+    /// Generated alternatives.
+    pub choices: Vec<ChatChoice>,
+    /// Token usage.
+    pub usage: Option<Usage>,
+    /// Fields not modelled above.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl ChatCompletion {
+    /// Text of the first choice.
+    pub fn text(&self) -> Option<&str> {
+        self.choices.first().map(|c| c.message.content.as_str())
+    }
+}
+
+/// Incremental message content in a [`ChatCompletionChunk`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatDelta {
+    /// Set on the first chunk of a message.
+    pub role: Option<Role>,
+    /// Text generated since the previous chunk.
+    pub content: Option<String>,
+    /// Fields not modelled above (e.g. partial `tool_calls`).
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// One alternative of a [`ChatCompletionChunk`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkChoice {
+    /// Position in `choices`.
+    #[serde(default)]
+    pub index: u32,
+    /// New content for this choice.
+    #[serde(default)]
+    pub delta: ChatDelta,
+    /// Set on the last chunk of this choice.
+    pub finish_reason: Option<String>,
+}
+
+/// One server-sent event of a streamed chat completion.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatCompletionChunk {
+    /// Completion ID (the same for every chunk).
+    #[serde(default)]
+    pub id: String,
+    /// Model producing the completion.
+    #[serde(default)]
+    pub model: String,
+    /// Content deltas; empty on the final usage-only chunk.
+    #[serde(default)]
+    pub choices: Vec<ChunkChoice>,
+    /// Token usage, on the final chunk.
+    pub usage: Option<Usage>,
+}
+
+impl ChatCompletionChunk {
+    /// Text added to the first choice by this chunk.
+    pub fn text(&self) -> &str {
+        self.choices.first().and_then(|c| c.delta.content.as_deref()).unwrap_or_default()
+    }
+}
+
+/// A model available through the gateway.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Model {
+    /// Model ID to pass as [`ChatCompletionRequest::model`].
+    pub id: String,
+    /// Provider or owner.
+    pub owned_by: Option<String>,
+    /// Creation time (Unix seconds).
+    pub created: Option<u64>,
+    /// Fields not modelled above (e.g. context length, pricing).
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModelList {
+    data: Vec<Model>,
+}
+
+fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::deserialize(deserializer)?.unwrap_or_default())
+}
+
+// ---------------------------------------------------------------------------
+// Client
+// ---------------------------------------------------------------------------
+
+/// Client for the Bankr LLM Gateway.
+#[derive(Debug)]
+pub struct LlmClient {
+    rest: RestClient<LlmAuth>,
+    retry: RetryPolicy,
+}
+
+impl LlmClient {
+    /// Create a client with the given LLM key and the default base URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BankrError::Config`] if the underlying HTTP client cannot be
+    /// created.
+    pub fn new(llm_key: &str) -> Result<Self, BankrError> {
+        Self::builder(llm_key).build()
+    }
+
+    /// Create a client with a custom base URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BankrError::Config`] if the underlying HTTP client cannot be
+    /// created.
+    pub fn with_base_url(llm_key: &str, base_url: &str) -> Result<Self, BankrError> {
+        Self::builder(llm_key).base_url(base_url).build()
+    }
+
+    /// Start building a client with custom transport options.
+    pub fn builder(llm_key: &str) -> LlmClientBuilder {
+        LlmClientBuilder::new(llm_key)
+    }
+
+    /// The retry policy applied to every request.
+    pub const fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry
+    }
+
+    /// List the models the key can use.
+    ///
+    /// `GET /v1/models`
+    pub async fn list_models(&self) -> Result<Vec<Model>, BankrError> {
+        debug!("GET /v1/models");
+        let list: ModelList = retry::run(&self.retry, Idempotency::Idempotent, || async {
+            self.rest.get("/v1/models").await.map(|resp| resp.data).map_err(BankrError::from)
+        })
+        .await?;
+        Ok(list.data)
+    }
+
+    /// Generate a chat completion.
+    ///
+    /// `POST /v1/chat/completions`
+    ///
+    /// Every completion is billed, so a request that may have reached the
+    /// gateway is only retried if [`RetryPolicy::retry_non_idempotent`] is
+    /// set.
+    pub async fn chat_completion(
+        &self,
+        req: &ChatCompletionRequest,
+    ) -> Result<ChatCompletion, BankrError> {
+        debug!(model = %req.model, "POST {CHAT_COMPLETIONS}");
+        let body = request_body(req, false)?;
+        retry::run(&self.retry, Idempotency::NonIdempotent, || async {
+            self.rest.post(CHAT_COMPLETIONS, &body).await.map(|r| r.data).map_err(BankrError::from)
+        })
+        .await
+    }
+
+    /// Generate a chat completion, streaming chunks as server-sent events.
+    ///
+    /// Opening the stream is retried like
+    /// [`chat_completion`](Self::chat_completion); errors after the first
+    /// byte end the stream with that error. The last chunk carries
+    /// [`Usage`] when the model reports it.
+    pub async fn chat_completion_stream(
+        &self,
+        req: &ChatCompletionRequest,
+    ) -> Result<
+        impl Stream<Item = Result<ChatCompletionChunk, BankrError>> + Send + use<>,
+        BankrError,
+    > {
+        debug!(model = %req.model, stream = true, "POST {CHAT_COMPLETIONS}");
+        let body = serde_json::to_vec(&request_body(req, true)?)
+            .map_err(|e| BankrError::Config(format!("cannot serialize request: {e}")))?;
+        let resp =
+            retry::run(&self.retry, Idempotency::NonIdempotent, || self.open_stream(&body)).await?;
+
+        Ok(stream::try_unfold(
+            (resp, SseDecoder::default()),
+            |(mut resp, mut decoder)| async move {
+                loop {
+                    if let Some(data) = decoder.next_event() {
+                        if data == "[DONE]" {
+                            return Ok(None);
+                        }
+                        let chunk = serde_json::from_str(&data).map_err(|e| {
+                            BankrError::Deserialization(format!(
+                                "invalid stream chunk {data:?}: {e}"
+                            ))
+                        })?;
+                        return Ok(Some((chunk, (resp, decoder))));
+                    }
+                    match resp.chunk().await.map_err(TransportError::from)? {
+                        Some(bytes) => decoder.push(&bytes),
+                        None => return Ok(None),
+                    }
+                }
+            },
+        ))
+    }
+
+    async fn open_stream(&self, body: &[u8]) -> Result<hpx::Response, BankrError> {
+        let mut headers = HeaderMap::new();
+        self.rest.auth().sign(&Method::POST, CHAT_COMPLETIONS, &mut headers, Some(body)).await?;
+        let url = format!("{}{CHAT_COMPLETIONS}", self.rest.base_url().trim_end_matches('/'));
+        let resp = self
+            .rest
+            .http()
+            .post(&url)
+            .headers(headers)
+            .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+            .header(ACCEPT, HeaderValue::from_static("text/event-stream"))
+            .body(body.to_vec())
+            .send()
+            .await
+            .map_err(TransportError::from)?;
+
+        let status = resp.status();
+        if !status.is_success() {
+            let text = resp.text().await.unwrap_or_default();
+            return Err(BankrError::from_response(status.as_u16(), &text));
+        }
+        Ok(resp)
+    }
+}
+
+/// Serialize `req` with `stream` set, asking for usage on streamed responses.
+fn request_body(req: &ChatCompletionRequest, stream: bool) -> Result<Value, BankrError> {
+    let mut body = serde_json::to_value(req)
+        .map_err(|e| BankrError::Config(format!("cannot serialize request: {e}")))?;
+    if let Some(obj) = body.as_object_mut() {
+        obj.insert("stream".to_owned(), Value::Bool(stream));
+        if stream {
+            obj.entry("stream_options").or_insert(serde_json::json!({ "include_usage": true }));
+        }
+    }
+    Ok(body)
+}
+
+/// Splits a byte stream into the `data` payloads of server-sent events.
+///
+/// Bytes are buffered until an event is complete, so a UTF-8 character split
+/// across network chunks is decoded intact.
+#[derive(Debug, Default)]
+struct SseDecoder {
+    buf: Vec<u8>,
+}
+
+impl SseDecoder {
+    fn push(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+
+    /// The data of the next complete event, skipping events without data
+    /// (comments and keep-alives).
+    fn next_event(&mut self) -> Option<String> {
+        while let Some((end, len)) = self.boundary() {
+            let event: Vec<u8> = self.buf.drain(..end + len).collect();
+            let event = String::from_utf8_lossy(&event[..end]);
+            let data: Vec<&str> = event
+                .split('\n')
+                .map(|line| line.strip_suffix('\r').unwrap_or(line))
+                .filter_map(|line| line.strip_prefix("data:"))
+                .map(|d| d.strip_prefix(' ').unwrap_or(d))
+                .collect();
+            if !data.is_empty() {
+                return Some(data.join("\n"));
+            }
+        }
+        None
+    }
+
+    /// Start and length of the first blank-line separator (`\n\n`,
+    /// `\r\n\r\n` or `\n\r\n`).
+    fn boundary(&self) -> Option<(usize, usize)> {
+        (0..self.buf.len()).find_map(|i| {
+            let rest = &self.buf[i..];
+            if rest.starts_with(b"\n\n") {
+                Some((i, 2))
+            } else if rest.starts_with(b"\n\r\n") {
+                Some((i, 3))
+            } else {
+                None
+            }
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Builder
+// ---------------------------------------------------------------------------
+
+/// Builder for [`LlmClient`].
+#[derive(Debug, Clone)]
+pub struct LlmClientBuilder {
+    llm_key: String,
+    base_url: String,
+    timeout: Duration,
+    user_agent_suffix: Option<String>,
+    retry: RetryPolicy,
+}
+
+impl LlmClientBuilder {
+    /// Create a builder with default settings for the given key.
+    pub fn new(llm_key: &str) -> Self {
+        Self {
+            llm_key: llm_key.to_owned(),
+            base_url: DEFAULT_BASE_URL.to_owned(),
+            timeout: DEFAULT_TIMEOUT,
+            user_agent_suffix: None,
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    /// Override the base URL (default: `https://llm.bankr.bot`).
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        base_url.clone_into(&mut self.base_url);
+        self
+    }
+
+    /// Total timeout for each HTTP request, including reading a streamed
+    /// response (default: 5 min).
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Append a product token to the `bankr-sdk-rs/<version>` user agent.
+    pub fn user_agent_suffix(mut self, suffix: &str) -> Self {
+        self.user_agent_suffix = Some(suffix.to_owned());
+        self
+    }
+
+    /// Retry policy for failed requests (default: [`RetryPolicy::default`]).
+    pub const fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Build the client.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BankrError::Config`] if the underlying HTTP client cannot be
+    /// created.
+    pub fn build(self) -> Result<LlmClient, BankrError> {
+        let user_agent = match &self.user_agent_suffix {
+            Some(suffix) => format!("{USER_AGENT} {suffix}"),
+            None => USER_AGENT.to_owned(),
+        };
+        let config = RestConfig::new(self.base_url).timeout(self.timeout).user_agent(user_agent);
+        let auth = CompositeAuth::new(
+            BearerAuth::new(self.llm_key.clone()),
+            ApiKeyAuth::header("X-API-Key", self.llm_key),
+        );
+        let rest = RestClient::new(config, auth).map_err(|e| BankrError::Config(e.to_string()))?;
+        Ok(LlmClient { rest, retry: self.retry })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sse_decoder_splits_events() {
+        let mut decoder = SseDecoder::default();
+        decoder.push(b": keep-alive\n\ndata: {\"a\":1}\r\n\r\ndata: [DO");
+        assert_eq!(decoder.next_event().as_deref(), Some("{\"a\":1}"));
+        assert_eq!(decoder.next_event(), None);
+        decoder.push(b"NE]\n\n");
+        assert_eq!(decoder.next_event().as_deref(), Some("[DONE]"));
+    }
+
+    #[test]
+    fn sse_decoder_keeps_split_utf8() {
+        // "é" is 0xC3 0xA9; the chunk boundary falls between the two bytes.
+        let mut decoder = SseDecoder::default();
+        decoder.push(b"data: caf\xC3");
+        assert_eq!(decoder.next_event(), None);
+        decoder.push(b"\xA9\r\n\r\ndata: \xE2\x82");
+        assert_eq!(decoder.next_event().as_deref(), Some("caf\u{e9}"));
+        decoder.push(b"\xAC\n\n");
+        assert_eq!(decoder.next_event().as_deref(), Some("\u{20ac}"));
+    }
+
+    #[test]
+    fn request_body_sets_stream() -> Result<(), BankrError> {
+        let mut req = ChatCompletionRequest::new("m", vec![ChatMessage::user("hi")]);
+        req.extra.insert("top_p".to_owned(), Value::from(0.5));
+
+        let body = request_body(&req, false)?;
+        assert_eq!(body["stream"], false);
+        assert_eq!(body["top_p"], 0.5);
+        assert_eq!(body["messages"][0], serde_json::json!({ "role": "user", "content": "hi" }));
+        assert!(body.get("max_tokens").is_none());
+
+        let body = request_body(&req, true)?;
+        assert_eq!(body["stream_options"]["include_usage"], true);
+        Ok(())
+    }
+
+    #[test]
+    fn null_content_deserializes_as_empty() -> Result<(), serde_json::Error> {
+        let completion: ChatCompletion = serde_json::from_str(
+            r#"{"id":"c1","model":"m","choices":[{"index":0,"message":{"role":"assistant","content":null,"tool_calls":[]},"finish_reason":"tool_calls"}],"usage":{"prompt_tokens":3,"completion_tokens":1,"total_tokens":4,"cost":0.1}}"#,
+        )?;
+        assert_eq!(completion.text(), Some(""));
+        assert!(completion.choices[0].message.extra.contains_key("tool_calls"));
+        assert_eq!(completion.usage.as_ref().map(|u| u.total_tokens), Some(4));
+        Ok(())
+    }
+}
@@ -0,0 +1,272 @@
+//! In-memory [`BankrApi`] implementation for tests.
+//!
+//! [`MockBankrApi`] returns scripted responses in FIFO order per endpoint and
+//! records every call, so tests can assert on the exact requests produced by
+//! the code under test.
+//!
+//! ```rust
+//! use bankr_agent_api::{
+//!     BankrApi,
+//!     mock::{MockBankrApi, MockCall},
+//!     types::{PersonalMessage, SignPayload, SignRequest, SignResponse, SignatureType},
+//! };
+//!
+//! # async fn example() -> Result<(), bankr_agent_api::error::BankrError> {
+//! let mock = MockBankrApi::new();
+//! mock.push_sign(Ok(SignResponse {
+//!     success: true,
+//!     signature: Some("0xsig".to_owned()),
+//!     signer: Some("0xsigner".to_owned()),
+//!     signature_type: Some(SignatureType::PersonalSign),
+//!     error: None,
+//! }));
+//!
+//! mock.sign(&SignRequest::personal("hello")).await?;
+//!
+//! assert!(matches!(
+//!     &mock.sign_requests()[0].payload,
+//!     SignPayload::PersonalSign(PersonalMessage::Text(text)) if text == "hello"
+//! ));
+//! assert!(matches!(mock.calls()[0], MockCall::Sign(_)));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::VecDeque,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use async_trait::async_trait;
+
+use crate::{
+    api::BankrApi,
+    error::BankrError,
+    types::{
+        CancelJobResponse, JobResponse, PromptRequest, PromptResponse, SignRequest, SignResponse,
+        SubmitRequest, SubmitResponse, UserInfoResponse,
+    },
+};
+
+/// A call recorded by [`MockBankrApi`].
+#[derive(Debug, Clone)]
+pub enum MockCall {
+    /// `get_me()`
+    GetMe,
+    /// `submit_prompt(req)`
+    SubmitPrompt(PromptRequest),
+    /// `get_job(job_id)`
+    GetJob(String),
+    /// `cancel_job(job_id)`
+    CancelJob(String),
+    /// `sign(req)`
+    Sign(SignRequest),
+    /// `submit_transaction(req)`
+    SubmitTransaction(SubmitRequest),
+}
+
+type Script<T> = VecDeque<Result<T, BankrError>>;
+
+#[derive(Debug, Default)]
+struct State {
+    me: Script<UserInfoResponse>,
+    prompts: Script<PromptResponse>,
+    jobs: Script<JobResponse>,
+    cancels: Script<CancelJobResponse>,
+    signs: Script<SignResponse>,
+    submits: Script<SubmitResponse>,
+    calls: Vec<MockCall>,
+}
+
+/// Scripted, call-recording implementation of [`BankrApi`].
+///
+/// Each endpoint has its own queue of responses. A call with an empty queue
+/// returns [`BankrError::Config`] describing the missing script entry.
+#[derive(Debug, Default)]
+pub struct MockBankrApi {
+    state: Mutex<State>,
+}
+
+impl MockBankrApi {
+    /// Create a mock with no scripted responses.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Queue a response for the next `get_me` call.
+    pub fn push_get_me(&self, resp: Result<UserInfoResponse, BankrError>) {
+        self.state().me.push_back(resp);
+    }
+
+    /// Queue a response for the next `submit_prompt` call.
+    pub fn push_submit_prompt(&self, resp: Result<PromptResponse, BankrError>) {
+        self.state().prompts.push_back(resp);
+    }
+
+    /// Queue a response for the next `get_job` call (also used by
+    /// `poll_job`).
+    pub fn push_get_job(&self, resp: Result<JobResponse, BankrError>) {
+        self.state().jobs.push_back(resp);
+    }
+
+    /// Queue a response for the next `cancel_job` call.
+    pub fn push_cancel_job(&self, resp: Result<CancelJobResponse, BankrError>) {
+        self.state().cancels.push_back(resp);
+    }
+
+    /// Queue a response for the next `sign` call.
+    pub fn push_sign(&self, resp: Result<SignResponse, BankrError>) {
+        self.state().signs.push_back(resp);
+    }
+
+    /// Queue a response for the next `submit_transaction` call.
+    pub fn push_submit_transaction(&self, resp: Result<SubmitResponse, BankrError>) {
+        self.state().submits.push_back(resp);
+    }
+
+    /// All calls made so far, in order.
+    pub fn calls(&self) -> Vec<MockCall> {
+        self.state().calls.clone()
+    }
+
+    /// Requests passed to `submit_prompt`, in order.
+    pub fn prompt_requests(&self) -> Vec<PromptRequest> {
+        self.recorded(|call| match call {
+            MockCall::SubmitPrompt(req) => Some(req.clone()),
+            _ => None,
+        })
+    }
+
+    /// Requests passed to `sign`, in order.
+    pub fn sign_requests(&self) -> Vec<SignRequest> {
+        self.recorded(|call| match call {
+            MockCall::Sign(req) => Some(req.clone()),
+            _ => None,
+        })
+    }
+
+    /// Requests passed to `submit_transaction`, in order.
+    pub fn submit_requests(&self) -> Vec<SubmitRequest> {
+        self.recorded(|call| match call {
+            MockCall::SubmitTransaction(req) => Some(req.clone()),
+            _ => None,
+        })
+    }
+
+    fn recorded<T>(&self, f: impl Fn(&MockCall) -> Option<T>) -> Vec<T> {
+        self.state().calls.iter().filter_map(f).collect()
+    }
+
+    /// Record `call` and pop the next scripted response from the queue
+    /// selected by `queue`.
+    fn respond<T>(
+        &self,
+        call: MockCall,
+        endpoint: &str,
+        queue: impl FnOnce(&mut State) -> &mut Script<T>,
+    ) -> Result<T, BankrError> {
+        let mut state = self.state();
+        state.calls.push(call);
+        queue(&mut state).pop_front().unwrap_or_else(|| {
+            Err(BankrError::Config(format!("MockBankrApi: no scripted response for {endpoint}")))
+        })
+    }
+}
+
+#[async_trait]
+impl BankrApi for MockBankrApi {
+    async fn get_me(&self) -> Result<UserInfoResponse, BankrError> {
+        self.respond(MockCall::GetMe, "get_me", |s| &mut s.me)
+    }
+
+    async fn submit_prompt(&self, req: &PromptRequest) -> Result<PromptResponse, BankrError> {
+        self.respond(MockCall::SubmitPrompt(req.clone()), "submit_prompt", |s| &mut s.prompts)
+    }
+
+    async fn get_job(&self, job_id: &str) -> Result<JobResponse, BankrError> {
+        self.respond(MockCall::GetJob(job_id.to_owned()), "get_job", |s| &mut s.jobs)
+    }
+
+    async fn cancel_job(&self, job_id: &str) -> Result<CancelJobResponse, BankrError> {
+        self.respond(MockCall::CancelJob(job_id.to_owned()), "cancel_job", |s| &mut s.cancels)
+    }
+
+    async fn sign(&self, req: &SignRequest) -> Result<SignResponse, BankrError> {
+        self.respond(MockCall::Sign(req.clone()), "sign", |s| &mut s.signs)
+    }
+
+    async fn submit_transaction(&self, req: &SubmitRequest) -> Result<SubmitResponse, BankrError> {
+        self.respond(MockCall::SubmitTransaction(req.clone()), "submit_transaction", |s| {
+            &mut s.submits
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::types::{EvmTransaction, JobStatus};
+
+    fn job(status: JobStatus) -> JobResponse {
+        JobResponse {
+            thread_id: Some("thr_1".to_owned()),
+            response: Some("ETH is $3,245.67".to_owned()),
+            ..JobResponse::new("job_1", status)
+        }
+    }
+
+    #[tokio::test]
+    async fn poll_job_uses_scripted_jobs() -> Result<(), BankrError> {
+        let mock = MockBankrApi::new();
+        mock.push_get_job(Ok(job(JobStatus::Pending)));
+        mock.push_get_job(Ok(job(JobStatus::Processing)));
+        mock.push_get_job(Ok(job(JobStatus::Completed)));
+
+        let done = mock.poll_job("job_1", Duration::ZERO, 5).await?;
+        assert_eq!(done.status, JobStatus::Completed);
+        assert_eq!(mock.calls().len(), 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn records_submit_requests() {
+        let mock = MockBankrApi::new();
+        let req = SubmitRequest {
+            transaction: EvmTransaction {
+                to: "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913".to_owned(),
+                chain_id: 8453,
+                value: Some("0".to_owned()),
+                data: None,
+                gas: None,
+                gas_price: None,
+                max_fee_per_gas: None,
+                max_priority_fee_per_gas: None,
+                nonce: None,
+            },
+            description: Some("test".to_owned()),
+            wait_for_confirmation: Some(true),
+        };
+
+        // Nothing scripted: the call is still recorded.
+        let result = mock.submit_transaction(&req).await;
+        assert!(matches!(result, Err(BankrError::Config(_))));
+
+        let recorded = mock.submit_requests();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].transaction.chain_id, 8453);
+        assert_eq!(recorded[0].description.as_deref(), Some("test"));
+    }
+
+    #[tokio::test]
+    async fn scripted_errors_are_returned() {
+        let mock = MockBankrApi::new();
+        mock.push_get_job(Err(BankrError::NotFound { body: Default::default() }));
+        assert!(matches!(mock.get_job("missing").await, Err(BankrError::NotFound { .. })));
+    }
+}
@@ -0,0 +1,175 @@
+//! Job polling configuration.
+//!
+//! [`PollConfig`] controls how [`BankrAgentClient`](crate::BankrAgentClient)
+//! waits for a job: the interval starts at `initial_interval` and grows by
+//! `multiplier` after each poll up to `max_interval`, until the job reaches a
+//! terminal state or the overall `deadline` (and/or `max_attempts`) runs out.
+//!
+//! On timeout, [`BankrError::PollTimeout`](crate::error::BankrError::PollTimeout)
+//! carries the last observed job, so callers can keep its `job_id` and
+//! `thread_id` and resume waiting later.
+
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+/// How to poll a job until it reaches a terminal state.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PollConfig {
+    /// Delay between the first and second poll.
+    pub initial_interval: Duration,
+    /// Factor applied to the interval after each poll.
+    pub multiplier: f64,
+    /// Upper bound for a single interval.
+    pub max_interval: Duration,
+    /// Overall time budget, measured from the first poll. `None` waits
+    /// indefinitely (subject to `max_attempts`).
+    pub deadline: Option<Duration>,
+    /// Maximum number of polls. `None` polls until the deadline. A wait
+    /// always polls at least once, so `Some(0)` behaves like `Some(1)`.
+    pub max_attempts: Option<u32>,
+}
+
+impl Default for PollConfig {
+    /// 1 s initial interval growing ×1.5 up to 10 s, for at most 2 minutes.
+    fn default() -> Self {
+        Self {
+            initial_interval: Duration::from_secs(1),
+            multiplier: 1.5,
+            max_interval: Duration::from_secs(10),
+            deadline: Some(Duration::from_mins(2)),
+            max_attempts: None,
+        }
+    }
+}
+
+impl PollConfig {
+    /// Poll every `interval`, at most `max_attempts` times (at least once),
+    /// with no deadline.
+    pub const fn fixed(interval: Duration, max_attempts: u32) -> Self {
+        Self {
+            initial_interval: interval,
+            multiplier: 1.0,
+            max_interval: interval,
+            deadline: None,
+            max_attempts: Some(max_attempts),
+        }
+    }
+
+    /// Set the overall deadline.
+    pub const fn deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Set the initial and maximum interval.
+    pub const fn interval(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_interval = initial;
+        self.max_interval = max;
+        self
+    }
+
+    /// Set the backoff multiplier.
+    pub const fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Set the maximum number of polls (`0` is treated as `1`).
+    pub const fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+}
+
+/// Tracks the attempts, elapsed time and next interval of one wait.
+#[derive(Debug)]
+pub(crate) struct PollSchedule {
+    config: PollConfig,
+    started: Instant,
+    attempts: u32,
+    next: Duration,
+}
+
+impl PollSchedule {
+    pub(crate) fn new(config: &PollConfig) -> Self {
+        Self {
+            config: config.clone(),
+            started: Instant::now(),
+            attempts: 0,
+            next: config.initial_interval,
+        }
+    }
+
+    /// Number of polls made so far.
+    pub(crate) const fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Record a poll and return how long to wait before the next one, or
+    /// `None` if the attempt or time budget is exhausted.
+    pub(crate) fn after_attempt(&mut self) -> Option<Duration> {
+        self.attempts += 1;
+        if self.config.max_attempts.is_some_and(|max| self.attempts >= max) {
+            return None;
+        }
+
+        let mut delay = self.next.min(self.config.max_interval);
+        let grown = self.next.as_secs_f64() * self.config.multiplier.max(1.0);
+        self.next = Duration::try_from_secs_f64(grown)
+            .unwrap_or(self.config.max_interval)
+            .min(self.config.max_interval);
+
+        if let Some(deadline) = self.config.deadline {
+            let remaining = deadline.saturating_sub(self.started.elapsed());
+            if remaining.is_zero() {
+                return None;
+            }
+            delay = delay.min(remaining);
+        }
+        Some(delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interval_grows_to_max() {
+        let config = PollConfig {
+            initial_interval: Duration::from_secs(1),
+            multiplier: 2.0,
+            max_interval: Duration::from_secs(5),
+            deadline: None,
+            max_attempts: None,
+        };
+        let mut schedule = PollSchedule::new(&config);
+        let delays: Vec<_> = (0..5).filter_map(|_| schedule.after_attempt()).collect();
+        assert_eq!(delays, [1, 2, 4, 5, 5].map(Duration::from_secs));
+    }
+
+    #[test]
+    fn fixed_stops_after_max_attempts() {
+        let mut schedule = PollSchedule::new(&PollConfig::fixed(Duration::from_secs(2), 3));
+        assert_eq!(schedule.after_attempt(), Some(Duration::from_secs(2)));
+        assert_eq!(schedule.after_attempt(), Some(Duration::from_secs(2)));
+        assert_eq!(schedule.after_attempt(), None);
+        assert_eq!(schedule.attempts(), 3);
+
+        // The first poll always happens; zero attempts means just that one.
+        let mut once = PollSchedule::new(&PollConfig::fixed(Duration::from_secs(2), 0));
+        assert_eq!(once.after_attempt(), None);
+        assert_eq!(once.attempts(), 1);
+    }
+
+    #[test]
+    fn delay_is_capped_by_deadline() {
+        let config = PollConfig::default().deadline(Duration::from_millis(300));
+        let mut schedule = PollSchedule::new(&config);
+        assert!(schedule.after_attempt().is_some_and(|d| d <= Duration::from_millis(300)));
+
+        let mut expired = PollSchedule::new(&PollConfig::default().deadline(Duration::ZERO));
+        assert_eq!(expired.after_attempt(), None);
+    }
+}
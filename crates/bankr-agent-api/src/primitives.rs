@@ -0,0 +1,577 @@
+//! Validated EVM primitives.
+//!
+//! [`Address`], [`U256`] and [`Bytes`] parse from, and serialize to, the
+//! string formats the Bankr API uses on the wire: checksummed `0x` addresses,
+//! decimal wei amounts and `0x`-prefixed hex calldata. Malformed values are
+//! rejected when parsed instead of by the server.
+//!
+//! ```rust
+//! use bankr_agent_api::primitives::{Address, U256};
+//!
+//! let usdc: Address = "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913".parse()?;
+//! assert_eq!(usdc.to_string(), "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913");
+//!
+//! let one_eth: U256 = "1000000000000000000".parse()?;
+//! assert_eq!(one_eth, U256::from(10u64.pow(18)));
+//! # Ok::<(), bankr_agent_api::primitives::PrimitiveError>(())
+//! ```
+
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+use sha3::{Digest, Keccak256};
+
+/// Error parsing an EVM primitive.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PrimitiveError {
+    /// Not `0x` followed by 40 hex digits.
+    #[error("invalid address `{0}`: expected 0x followed by 40 hex digits")]
+    InvalidAddress(String),
+    /// Mixed-case address whose EIP-55 checksum does not match.
+    #[error("address `{0}` has an invalid EIP-55 checksum")]
+    InvalidChecksum(String),
+    /// Not a decimal or `0x`-prefixed hex integer.
+    #[error("invalid amount `{0}`: expected a decimal or 0x-prefixed hex integer")]
+    InvalidAmount(String),
+    /// Integer larger than 2^256 - 1.
+    #[error("amount `{0}` does not fit in 256 bits")]
+    Overflow(String),
+    /// Not `0x` followed by an even number of hex digits.
+    #[error("invalid hex data `{0}`: expected 0x followed by an even number of hex digits")]
+    InvalidHex(String),
+}
+
+/// Keccak-256 hash of `data`.
+pub(crate) fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+/// Lowercase hex encoding of `bytes`, without a `0x` prefix.
+pub(crate) fn encode_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        out.push(char::from(DIGITS[usize::from(b >> 4)]));
+        out.push(char::from(DIGITS[usize::from(b & 0x0f)]));
+    }
+    out
+}
+
+/// Decode hex digits (no prefix). `None` on odd length or non-hex input.
+pub(crate) fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    hex.as_bytes()
+        .chunks_exact(2)
+        .map(|pair| {
+            let hi = char::from(pair[0]).to_digit(16)?;
+            let lo = char::from(pair[1]).to_digit(16)?;
+            Some((hi * 16 + lo) as u8)
+        })
+        .collect()
+}
+
+/// Serialize via `Display` and deserialize via `FromStr`.
+macro_rules! string_serde {
+    ($ty:ty, $expecting:literal) => {
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct Visitor;
+
+                impl de::Visitor<'_> for Visitor {
+                    type Value = $ty;
+
+                    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        f.write_str($expecting)
+                    }
+
+                    fn visit_str<E: de::Error>(self, v: &str) -> Result<$ty, E> {
+                        v.parse().map_err(E::custom)
+                    }
+
+                    fn visit_u64<E: de::Error>(self, v: u64) -> Result<$ty, E> {
+                        v.to_string().parse().map_err(E::custom)
+                    }
+                }
+
+                deserializer.deserialize_any(Visitor)
+            }
+        }
+    };
+}
+
+// ---------------------------------------------------------------------------
+// Address
+// ---------------------------------------------------------------------------
+
+/// A 20-byte EVM address.
+///
+/// Parsing accepts all-lowercase and all-uppercase hex, and mixed case only
+/// with a valid EIP-55 checksum. `Display` and `Serialize` produce the
+/// checksummed form.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Address([u8; 20]);
+
+impl Address {
+    /// The zero address.
+    pub const ZERO: Self = Self([0; 20]);
+
+    /// Wrap raw address bytes.
+    pub const fn from_bytes(bytes: [u8; 20]) -> Self {
+        Self(bytes)
+    }
+
+    /// The raw address bytes.
+    pub const fn as_bytes(&self) -> &[u8; 20] {
+        &self.0
+    }
+
+    /// The EIP-55 checksummed `0x` form.
+    pub fn to_checksum(&self) -> String {
+        let lower = encode_hex(&self.0);
+        let hash = keccak256(lower.as_bytes());
+        let mut out = String::with_capacity(42);
+        out.push_str("0x");
+        for (i, c) in lower.chars().enumerate() {
+            let nibble = if i % 2 == 0 { hash[i / 2] >> 4 } else { hash[i / 2] & 0x0f };
+            out.push(if nibble >= 8 { c.to_ascii_uppercase() } else { c });
+        }
+        out
+    }
+}
+
+impl FromStr for Address {
+    type Err = PrimitiveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || PrimitiveError::InvalidAddress(s.to_owned());
+        let hex = s.strip_prefix("0x").ok_or_else(invalid)?;
+        if hex.len() != 40 {
+            return Err(invalid());
+        }
+        let bytes: [u8; 20] =
+            decode_hex(hex).and_then(|b| b.try_into().ok()).ok_or_else(invalid)?;
+        let address = Self(bytes);
+
+        let has_lower = hex.bytes().any(|b| b.is_ascii_lowercase());
+        let has_upper = hex.bytes().any(|b| b.is_ascii_uppercase());
+        if has_lower && has_upper && address.to_checksum() != s {
+            return Err(PrimitiveError::InvalidChecksum(s.to_owned()));
+        }
+        Ok(address)
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_checksum())
+    }
+}
+
+impl fmt::Debug for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Address({self})")
+    }
+}
+
+impl From<[u8; 20]> for Address {
+    fn from(bytes: [u8; 20]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<Address> for String {
+    fn from(address: Address) -> Self {
+        address.to_checksum()
+    }
+}
+
+string_serde!(Address, "a 0x-prefixed 20-byte hex address");
+
+// ---------------------------------------------------------------------------
+// U256
+// ---------------------------------------------------------------------------
+
+/// An unsigned 256-bit integer, e.g. a wei amount or gas value.
+///
+/// Parses from decimal or `0x`-prefixed hex; `Display` and `Serialize`
+/// produce decimal, as the API expects for `value`, `gas` and fee fields.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct U256([u64; 4]);
+
+impl U256 {
+    /// Zero.
+    pub const ZERO: Self = Self([0; 4]);
+    /// 2^256 - 1.
+    pub const MAX: Self = Self([u64::MAX; 4]);
+
+    /// Whether the value is zero.
+    pub const fn is_zero(&self) -> bool {
+        self.0[0] == 0 && self.0[1] == 0 && self.0[2] == 0 && self.0[3] == 0
+    }
+
+    /// Big-endian 32-byte representation.
+    pub fn to_be_bytes(&self) -> [u8; 32] {
+        let mut out = [0; 32];
+        for (i, limb) in self.0.iter().enumerate() {
+            out[24 - i * 8..32 - i * 8].copy_from_slice(&limb.to_be_bytes());
+        }
+        out
+    }
+
+    /// Parse big-endian bytes. `None` if more than 32 significant bytes.
+    pub fn from_be_slice(bytes: &[u8]) -> Option<Self> {
+        let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+        let bytes = &bytes[start..];
+        if bytes.len() > 32 {
+            return None;
+        }
+        let mut padded = [0; 32];
+        padded[32 - bytes.len()..].copy_from_slice(bytes);
+        let mut limbs = [0; 4];
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let mut chunk = [0; 8];
+            chunk.copy_from_slice(&padded[24 - i * 8..32 - i * 8]);
+            *limb = u64::from_be_bytes(chunk);
+        }
+        Some(Self(limbs))
+    }
+
+    /// Number of significant bits (0 for zero).
+    pub fn bits(&self) -> u32 {
+        self.0
+            .iter()
+            .rposition(|limb| *limb != 0)
+            .map_or(0, |i| i as u32 * 64 + (64 - self.0[i].leading_zeros()))
+    }
+
+    /// Two's-complement negation modulo 2^256.
+    pub fn wrapping_neg(self) -> Self {
+        let inverted = Self(self.0.map(|limb| !limb));
+        inverted.checked_add(Self::from(1u64)).unwrap_or(Self::ZERO)
+    }
+
+    /// `self + rhs`, or `None` on overflow.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        let mut out = [0; 4];
+        let mut carry = false;
+        for (i, limb) in out.iter_mut().enumerate() {
+            let (sum, c1) = self.0[i].overflowing_add(rhs.0[i]);
+            let (sum, c2) = sum.overflowing_add(u64::from(carry));
+            *limb = sum;
+            carry = c1 || c2;
+        }
+        (!carry).then_some(Self(out))
+    }
+
+    /// `self * rhs`, or `None` on overflow.
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        let mut out = [0u128; 8];
+        for (i, a) in self.0.iter().enumerate() {
+            let mut carry = 0u128;
+            for (j, b) in rhs.0.iter().enumerate() {
+                let cur = out[i + j] + u128::from(*a) * u128::from(*b) + carry;
+                out[i + j] = cur & u128::from(u64::MAX);
+                carry = cur >> 64;
+            }
+            out[i + 4] += carry;
+        }
+        if out[4..].iter().any(|limb| *limb != 0) {
+            return None;
+        }
+        Some(Self([out[0] as u64, out[1] as u64, out[2] as u64, out[3] as u64]))
+    }
+
+    /// Divide by a non-zero `u64`, returning quotient and remainder.
+    fn div_rem_u64(self, divisor: u64) -> (Self, u64) {
+        let mut out = [0; 4];
+        let mut rem = 0u128;
+        for i in (0..4).rev() {
+            let cur = (rem << 64) | u128::from(self.0[i]);
+            out[i] = (cur / u128::from(divisor)) as u64;
+            rem = cur % u128::from(divisor);
+        }
+        (Self(out), rem as u64)
+    }
+
+    fn parse_radix(digits: &str, radix: u32, original: &str) -> Result<Self, PrimitiveError> {
+        if digits.is_empty() {
+            return Err(PrimitiveError::InvalidAmount(original.to_owned()));
+        }
+        let base = Self::from(u64::from(radix));
+        digits.chars().try_fold(Self::ZERO, |acc, c| {
+            let digit = c
+                .to_digit(radix)
+                .ok_or_else(|| PrimitiveError::InvalidAmount(original.to_owned()))?;
+            acc.checked_mul(base)
+                .and_then(|v| v.checked_add(Self::from(u64::from(digit))))
+                .ok_or_else(|| PrimitiveError::Overflow(original.to_owned()))
+        })
+    }
+}
+
+impl FromStr for U256 {
+    type Err = PrimitiveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            Some(hex) => Self::parse_radix(hex, 16, s),
+            None => Self::parse_radix(s, 10, s),
+        }
+    }
+}
+
+impl fmt::Display for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+        let mut chunks = Vec::new();
+        let mut rest = *self;
+        loop {
+            let (quotient, rem) = rest.div_rem_u64(CHUNK);
+            chunks.push(rem);
+            if quotient.is_zero() {
+                break;
+            }
+            rest = quotient;
+        }
+        let mut out = String::new();
+        for (i, chunk) in chunks.iter().rev().enumerate() {
+            if i == 0 {
+                out.push_str(&chunk.to_string());
+            } else {
+                out.push_str(&format!("{chunk:019}"));
+            }
+        }
+        f.pad_integral(true, "", &out)
+    }
+}
+
+impl fmt::Debug for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl Ord for U256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl PartialOrd for U256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<u64> for U256 {
+    fn from(value: u64) -> Self {
+        Self([value, 0, 0, 0])
+    }
+}
+
+impl From<u128> for U256 {
+    fn from(value: u128) -> Self {
+        Self([value as u64, (value >> 64) as u64, 0, 0])
+    }
+}
+
+impl TryFrom<U256> for u128 {
+    type Error = PrimitiveError;
+
+    fn try_from(value: U256) -> Result<Self, Self::Error> {
+        if value.0[2] != 0 || value.0[3] != 0 {
+            return Err(PrimitiveError::Overflow(value.to_string()));
+        }
+        Ok(Self::from(value.0[0]) | (Self::from(value.0[1]) << 64))
+    }
+}
+
+impl From<U256> for String {
+    fn from(value: U256) -> Self {
+        value.to_string()
+    }
+}
+
+string_serde!(U256, "a decimal or 0x-prefixed hex integer");
+
+// ---------------------------------------------------------------------------
+// Bytes
+// ---------------------------------------------------------------------------
+
+/// Arbitrary bytes such as calldata, written as `0x`-prefixed hex.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct Bytes(Vec<u8>);
+
+impl Bytes {
+    /// The raw bytes.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Consume into the raw bytes.
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0
+    }
+
+    /// Number of bytes.
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether there are no bytes.
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromStr for Bytes {
+    type Err = PrimitiveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.strip_prefix("0x")
+            .and_then(decode_hex)
+            .map(Self)
+            .ok_or_else(|| PrimitiveError::InvalidHex(s.to_owned()))
+    }
+}
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{}", encode_hex(&self.0))
+    }
+}
+
+impl fmt::Debug for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Bytes({self})")
+    }
+}
+
+impl AsRef<[u8]> for Bytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for Bytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<&[u8]> for Bytes {
+    fn from(bytes: &[u8]) -> Self {
+        Self(bytes.to_vec())
+    }
+}
+
+impl From<Bytes> for String {
+    fn from(bytes: Bytes) -> Self {
+        bytes.to_string()
+    }
+}
+
+string_serde!(Bytes, "0x-prefixed hex data");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn address_checksum() -> Result<(), PrimitiveError> {
+        // Test vectors from EIP-55.
+        for s in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            let address: Address = s.parse()?;
+            assert_eq!(address.to_string(), s);
+            assert_eq!(s.to_lowercase().parse::<Address>()?, address);
+        }
+
+        assert!(matches!(
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD".parse::<Address>(),
+            Err(PrimitiveError::InvalidChecksum(_))
+        ));
+        for bad in [
+            "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beae",
+            "5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+        ] {
+            assert!(matches!(bad.parse::<Address>(), Err(PrimitiveError::InvalidAddress(_))));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn u256_parse_display_and_bounds() -> Result<(), PrimitiveError> {
+        let max = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+        assert_eq!(max.parse::<U256>()?, U256::MAX);
+        assert_eq!(U256::MAX.to_string(), max);
+        assert_eq!(format!("0x{}", "f".repeat(64)).parse::<U256>()?, U256::MAX);
+        assert!(matches!(
+            "115792089237316195423570985008687907853269984665640564039457584007913129639936"
+                .parse::<U256>(),
+            Err(PrimitiveError::Overflow(_))
+        ));
+
+        assert_eq!("0x2a".parse::<U256>()?, U256::from(42u64));
+        assert_eq!(U256::ZERO.to_string(), "0");
+        assert_eq!(U256::from(10_000_000_000_000_000_000u128).to_string(), "10000000000000000000");
+        assert!(U256::from(u128::MAX) < U256::MAX);
+        for bad in ["", "1.5", "-1", "0x", "12a"] {
+            assert!(matches!(bad.parse::<U256>(), Err(PrimitiveError::InvalidAmount(_))), "{bad}");
+        }
+
+        assert_eq!(U256::ZERO.bits(), 0);
+        assert_eq!(U256::from(u64::MAX).bits(), 64);
+        assert_eq!(U256::MAX.bits(), 256);
+        assert_eq!(U256::from(1u64).wrapping_neg(), U256::MAX);
+        assert_eq!(U256::ZERO.wrapping_neg(), U256::ZERO);
+
+        let value = U256::from(0x0102_u64);
+        assert_eq!(U256::from_be_slice(&value.to_be_bytes()), Some(value));
+        Ok(())
+    }
+
+    #[test]
+    fn bytes_round_trip() -> Result<(), PrimitiveError> {
+        let data: Bytes = "0xa9059CBB".parse()?;
+        assert_eq!(data.as_slice(), [0xa9, 0x05, 0x9c, 0xbb]);
+        assert_eq!(data.to_string(), "0xa9059cbb");
+        assert!("0x".parse::<Bytes>()?.is_empty());
+        for bad in ["a9059cbb", "0xabc", "0xzz"] {
+            assert!(matches!(bad.parse::<Bytes>(), Err(PrimitiveError::InvalidHex(_))));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn serde_uses_wire_strings() -> Result<(), serde_json::Error> {
+        let json = serde_json::json!([
+            "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+            "1000000000000000000",
+            "0xdeadbeef"
+        ]);
+        let (address, value, data): (Address, U256, Bytes) = serde_json::from_value(json)?;
+        assert_eq!(
+            serde_json::to_value((address, value, data))?,
+            serde_json::json!([
+                "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+                "1000000000000000000",
+                "0xdeadbeef"
+            ])
+        );
+        assert_eq!(serde_json::from_value::<U256>(serde_json::json!(21000))?, U256::from(21000u64));
+        Ok(())
+    }
+}
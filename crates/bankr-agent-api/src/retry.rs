@@ -0,0 +1,298 @@
+//! Retry policy for transient failures.
+//!
+//! [`BankrAgentClient`](crate::BankrAgentClient) and
+//! [`LlmClient`](crate::llm::LlmClient) retry requests that fail with
+//! transient transport errors, 5xx responses, or 429 rate limits, using
+//! exponential backoff with full jitter. Daily message limits (429 responses
+//! carrying `resetAt`) are handled according to [`DailyLimitBehavior`].
+//!
+//! Non-idempotent calls (`submit_prompt`, `submit_transaction`) are only
+//! retried when the request provably never reached the server (connection
+//! failures) or was explicitly rejected before processing (429), unless
+//! [`RetryPolicy::retry_non_idempotent`] is set.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tracing::warn;
+
+use crate::error::BankrError;
+
+/// What to do when the daily message limit is exhausted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DailyLimitBehavior {
+    /// Return [`BankrError::RateLimited`] immediately; the caller can inspect
+    /// `reset_at` to decide when to try again.
+    Fail,
+    /// Sleep until the reported `resetAt` and retry, provided the wait is no
+    /// longer than `max_wait`. Longer waits fail immediately.
+    WaitUntilReset {
+        /// Longest acceptable wait before giving up.
+        max_wait: Duration,
+    },
+}
+
+/// Whether a request may safely be sent more than once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Idempotency {
+    /// Repeating the request has no additional effect (GET, sign, cancel).
+    Idempotent,
+    /// Repeating the request may create a second job or transaction.
+    NonIdempotent,
+}
+
+/// Retry policy applied to every request made by the client.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the initial attempt. `0` disables
+    /// retries.
+    pub max_retries: u32,
+    /// Backoff before the first retry.
+    pub initial_backoff: Duration,
+    /// Upper bound for a single backoff.
+    pub max_backoff: Duration,
+    /// Factor applied to the backoff after each retry.
+    pub multiplier: f64,
+    /// Randomise each backoff between zero and its computed value.
+    pub jitter: bool,
+    /// How daily-limit 429 responses are handled.
+    pub daily_limit: DailyLimitBehavior,
+    /// Also retry non-idempotent requests after failures where the request
+    /// may already have reached the server (5xx, timeouts, dropped
+    /// connections). This can create duplicate jobs or transactions.
+    pub retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: true,
+            daily_limit: DailyLimitBehavior::Fail,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self { max_retries: 0, ..Self::default() }
+    }
+
+    /// Set the maximum number of retries.
+    pub const fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the initial and maximum backoff.
+    pub const fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Set the daily-limit behaviour.
+    pub const fn daily_limit(mut self, behavior: DailyLimitBehavior) -> Self {
+        self.daily_limit = behavior;
+        self
+    }
+
+    /// Opt in to retrying non-idempotent requests after ambiguous failures.
+    pub const fn retry_non_idempotent(mut self, enabled: bool) -> Self {
+        self.retry_non_idempotent = enabled;
+        self
+    }
+
+    /// Decide whether the failed attempt number `attempt` (starting at 1)
+    /// should be retried, and if so how long to wait first.
+    pub fn retry_delay(
+        &self,
+        err: &BankrError,
+        attempt: u32,
+        idempotency: Idempotency,
+    ) -> Option<Duration> {
+        if attempt > self.max_retries {
+            return None;
+        }
+
+        match err {
+            // Daily message limit: the response carries the reset time.
+            BankrError::RateLimited { reset_at: Some(reset_at), .. } => match self.daily_limit {
+                DailyLimitBehavior::Fail => None,
+                DailyLimitBehavior::WaitUntilReset { max_wait } => {
+                    let wait = until_unix_ms(*reset_at);
+                    (wait <= max_wait).then_some(wait)
+                }
+            },
+            // General per-minute limit: rejected before processing, so safe
+            // to repeat even for non-idempotent calls.
+            BankrError::RateLimited { reset_at: None, .. } |
+            // The connection was never established, so nothing was sent.
+            BankrError::Connect(_) => Some(self.backoff_for(attempt)),
+            BankrError::Transport(_) | BankrError::Api { status: 500..=599, .. } => {
+                let retryable = idempotency == Idempotency::Idempotent || self.retry_non_idempotent;
+                retryable.then(|| self.backoff_for(attempt))
+            }
+            _ => None,
+        }
+    }
+
+    /// Exponential backoff for retry number `attempt` (starting at 1).
+    ///
+    /// The fields are public, so the result is clamped to
+    /// `0..=max_backoff`: a negative or NaN multiplier gives no wait and an
+    /// overflowing one gives `max_backoff`.
+    fn backoff_for(&self, attempt: u32) -> Duration {
+        let exp = self.multiplier.powi(attempt.saturating_sub(1).min(i32::MAX as u32) as i32);
+        let max = self.max_backoff.as_secs_f64();
+        let secs = self.initial_backoff.as_secs_f64() * exp;
+        let secs = if secs.is_nan() { 0.0 } else { secs.clamp(0.0, max) };
+        let secs = if self.jitter { rand::random_range(0.0..=secs) } else { secs };
+        Duration::try_from_secs_f64(secs).unwrap_or(self.max_backoff)
+    }
+}
+
+/// Run `request`, retrying failed attempts as `policy` allows.
+pub(crate) async fn run<T, F, Fut>(
+    policy: &RetryPolicy,
+    idempotency: Idempotency,
+    request: F,
+) -> Result<T, BankrError>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T, BankrError>>,
+{
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        let err = match request().await {
+            Ok(value) => return Ok(value),
+            Err(e) => e,
+        };
+        let Some(delay) = policy.retry_delay(&err, attempt, idempotency) else {
+            return Err(err);
+        };
+        warn!(attempt, ?delay, error = %err, "Request failed, retrying");
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// Time remaining until the given Unix timestamp in milliseconds.
+fn until_unix_ms(reset_at: u64) -> Duration {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    Duration::from_millis(reset_at).saturating_sub(now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ApiErrorBody;
+
+    fn no_jitter() -> RetryPolicy {
+        RetryPolicy { jitter: false, ..RetryPolicy::default() }
+    }
+
+    fn api(status: u16) -> BankrError {
+        BankrError::Api { status, body: ApiErrorBody::default() }
+    }
+
+    fn rate_limited(reset_at: Option<u64>) -> BankrError {
+        BankrError::RateLimited { reset_at, limit: None, used: None, body: ApiErrorBody::default() }
+    }
+
+    #[test]
+    fn exponential_backoff_is_capped() {
+        let policy = no_jitter()
+            .max_retries(10)
+            .backoff(Duration::from_millis(100), Duration::from_millis(500));
+        let delays: Vec<_> =
+            (1..=5).map(|n| policy.retry_delay(&api(503), n, Idempotency::Idempotent)).collect();
+        assert_eq!(
+            delays,
+            [100, 200, 400, 500, 500].map(|ms| Some(Duration::from_millis(ms))).to_vec()
+        );
+    }
+
+    #[test]
+    fn unusual_multipliers_stay_within_bounds() {
+        let max = Duration::from_secs(5);
+        for multiplier in [-2.0, 0.0, f64::NAN, f64::INFINITY, 1e300] {
+            for jitter in [false, true] {
+                let policy = RetryPolicy { multiplier, jitter, ..RetryPolicy::default() }
+                    .max_retries(100)
+                    .backoff(Duration::from_secs(1), max);
+                for attempt in [1, 2, 3, 100] {
+                    let delay = policy.retry_delay(&api(503), attempt, Idempotency::Idempotent);
+                    assert!(delay.is_some_and(|d| d <= max), "{multiplier} {attempt}: {delay:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn gives_up_after_max_retries() {
+        let policy = no_jitter().max_retries(2);
+        assert!(policy.retry_delay(&api(500), 2, Idempotency::Idempotent).is_some());
+        assert!(policy.retry_delay(&api(500), 3, Idempotency::Idempotent).is_none());
+        assert!(RetryPolicy::none().retry_delay(&api(500), 1, Idempotency::Idempotent).is_none());
+    }
+
+    #[test]
+    fn jitter_stays_within_bounds() {
+        let policy = RetryPolicy::default();
+        for attempt in 1..=3 {
+            let delay = policy.retry_delay(&api(502), attempt, Idempotency::Idempotent);
+            assert!(delay.is_some_and(|d| d <= policy.max_backoff));
+        }
+    }
+
+    #[test]
+    fn client_errors_are_not_retried() {
+        let policy = no_jitter();
+        assert!(policy.retry_delay(&api(400), 1, Idempotency::Idempotent).is_none());
+        let config = BankrError::Config("invalid header value".to_owned());
+        assert!(policy.retry_delay(&config, 1, Idempotency::Idempotent).is_none());
+        let unauthorized = BankrError::Unauthorized { body: ApiErrorBody::default() };
+        assert!(policy.retry_delay(&unauthorized, 1, Idempotency::Idempotent).is_none());
+    }
+
+    #[test]
+    fn non_idempotent_requests_need_opt_in() {
+        let policy = no_jitter();
+        let timeout = BankrError::Transport("timed out".to_owned());
+        assert!(policy.retry_delay(&api(500), 1, Idempotency::NonIdempotent).is_none());
+        assert!(policy.retry_delay(&timeout, 1, Idempotency::NonIdempotent).is_none());
+
+        // Nothing reached the server, or the server rejected it outright.
+        let refused = BankrError::Connect("connection refused".to_owned());
+        assert!(policy.retry_delay(&refused, 1, Idempotency::NonIdempotent).is_some());
+        assert!(policy.retry_delay(&rate_limited(None), 1, Idempotency::NonIdempotent).is_some());
+
+        let policy = policy.retry_non_idempotent(true);
+        assert!(policy.retry_delay(&api(500), 1, Idempotency::NonIdempotent).is_some());
+    }
+
+    #[test]
+    fn daily_limit_behavior() {
+        let now_ms = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis();
+        let soon = rate_limited(Some(now_ms as u64 + 1_000));
+        let later = rate_limited(Some(now_ms as u64 + 3_600_000));
+
+        let fail = no_jitter();
+        assert!(fail.retry_delay(&soon, 1, Idempotency::NonIdempotent).is_none());
+
+        let wait = no_jitter()
+            .daily_limit(DailyLimitBehavior::WaitUntilReset { max_wait: Duration::from_mins(1) });
+        let delay = wait.retry_delay(&soon, 1, Idempotency::NonIdempotent);
+        assert!(delay.is_some_and(|d| d <= Duration::from_secs(1)));
+        assert!(wait.retry_delay(&later, 1, Idempotency::NonIdempotent).is_none());
+
+        // A reset time in the past means the quota is already available.
+        let past = rate_limited(Some(0));
+        assert_eq!(wait.retry_delay(&past, 1, Idempotency::NonIdempotent), Some(Duration::ZERO));
+    }
+}
@@ -0,0 +1,170 @@
+//! Minimal RLP decoding and encoding for signed transactions.
+
+/// A decoded RLP item, borrowing from the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Item<'a> {
+    /// The full encoding of this item, header included.
+    pub(crate) raw: &'a [u8],
+    /// The decoded value.
+    pub(crate) value: Value<'a>,
+}
+
+/// The value of an RLP item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Value<'a> {
+    /// A byte string.
+    Bytes(&'a [u8]),
+    /// A list of items.
+    List(Vec<Item<'a>>),
+}
+
+impl<'a> Item<'a> {
+    /// The byte string, or an error naming `field` if this is a list.
+    pub(crate) fn bytes(&self, field: &str) -> Result<&'a [u8], String> {
+        match self.value {
+            Value::Bytes(bytes) => Ok(bytes),
+            Value::List(_) => Err(format!("{field}: expected a byte string, found a list")),
+        }
+    }
+
+    /// The list items, or an error naming `field` if this is a byte string.
+    pub(crate) fn list(&self, field: &str) -> Result<&[Self], String> {
+        match &self.value {
+            Value::List(items) => Ok(items),
+            Value::Bytes(_) => Err(format!("{field}: expected a list, found a byte string")),
+        }
+    }
+}
+
+/// Decode exactly one item spanning all of `input`.
+pub(crate) fn decode(input: &[u8]) -> Result<Item<'_>, String> {
+    let (item, rest) = decode_one(input)?;
+    if !rest.is_empty() {
+        return Err(format!("{} trailing bytes after RLP item", rest.len()));
+    }
+    Ok(item)
+}
+
+fn decode_one(input: &[u8]) -> Result<(Item<'_>, &[u8]), String> {
+    let (&prefix, after) = input.split_first().ok_or("unexpected end of RLP input")?;
+    let (is_list, header, len) = match prefix {
+        0x00..=0x7f => {
+            let (raw, rest) = input.split_at(1);
+            return Ok((Item { raw, value: Value::Bytes(raw) }, rest));
+        }
+        0x80..=0xb7 => (false, 1, usize::from(prefix - 0x80)),
+        0xb8..=0xbf => (false, 1 + usize::from(prefix - 0xb7), long_length(after, prefix - 0xb7)?),
+        0xc0..=0xf7 => (true, 1, usize::from(prefix - 0xc0)),
+        0xf8..=0xff => (true, 1 + usize::from(prefix - 0xf7), long_length(after, prefix - 0xf7)?),
+    };
+    let end = header
+        .checked_add(len)
+        .filter(|end| *end <= input.len())
+        .ok_or("RLP length exceeds input")?;
+    let (raw, rest) = input.split_at(end);
+    let payload = &raw[header..];
+
+    let value = if is_list {
+        let mut items = Vec::new();
+        let mut remaining = payload;
+        while !remaining.is_empty() {
+            let (item, next) = decode_one(remaining)?;
+            items.push(item);
+            remaining = next;
+        }
+        Value::List(items)
+    } else {
+        if len == 1 && payload[0] < 0x80 {
+            return Err("non-canonical RLP: single byte below 0x80 with a length prefix".to_owned());
+        }
+        Value::Bytes(payload)
+    };
+    Ok((Item { raw, value }, rest))
+}
+
+/// Read a big-endian length of `size` bytes that follows a long-form prefix.
+fn long_length(input: &[u8], size: u8) -> Result<usize, String> {
+    let bytes = input.get(..usize::from(size)).ok_or("unexpected end of RLP input")?;
+    if bytes.first() == Some(&0) {
+        return Err("non-canonical RLP: length with leading zero".to_owned());
+    }
+    let len = bytes
+        .iter()
+        .try_fold(0usize, |acc, b| acc.checked_mul(256).map(|acc| acc + usize::from(*b)));
+    match len {
+        Some(len) if len >= 56 => Ok(len),
+        Some(_) => Err("non-canonical RLP: long form used for a short length".to_owned()),
+        None => Err("RLP length overflows".to_owned()),
+    }
+}
+
+/// Append the encoding of a byte string.
+pub(crate) fn encode_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    match bytes {
+        [b] if *b < 0x80 => out.push(*b),
+        _ => {
+            encode_header(out, 0x80, bytes.len());
+            out.extend_from_slice(bytes);
+        }
+    }
+}
+
+/// Append the encoding of an unsigned integer (big-endian, no leading zeros).
+pub(crate) fn encode_uint(out: &mut Vec<u8>, value: u64) {
+    let bytes = value.to_be_bytes();
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    encode_bytes(out, &bytes[start..]);
+}
+
+/// Wrap already-encoded items in a list.
+pub(crate) fn encode_list(payload: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(payload.len() + 9);
+    encode_header(&mut out, 0xc0, payload.len());
+    out.extend_from_slice(payload);
+    out
+}
+
+fn encode_header(out: &mut Vec<u8>, offset: u8, len: usize) {
+    if len < 56 {
+        out.push(offset + len as u8);
+    } else {
+        let bytes = len.to_be_bytes();
+        let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+        out.push(offset + 55 + (bytes.len() - start) as u8);
+        out.extend_from_slice(&bytes[start..]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() -> Result<(), String> {
+        let mut payload = Vec::new();
+        encode_bytes(&mut payload, b"dog");
+        encode_uint(&mut payload, 0);
+        encode_uint(&mut payload, 1024);
+        encode_bytes(&mut payload, &[0xaa; 60]);
+        let encoded = encode_list(&payload);
+        assert_eq!(&encoded[..9], [0xf8, 0x46, 0x83, b'd', b'o', b'g', 0x80, 0x82, 0x04]);
+
+        let item = decode(&encoded)?;
+        let items = item.list("root")?;
+        assert_eq!(items.len(), 4);
+        assert_eq!(items[0].bytes("0")?, b"dog");
+        assert_eq!(items[1].bytes("1")?, b"");
+        assert_eq!(items[3].bytes("3")?.len(), 60);
+        assert_eq!(item.raw, encoded.as_slice());
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        assert!(decode(&[]).is_err());
+        assert!(decode(&[0x83, b'd', b'o']).is_err());
+        assert!(decode(&[0x81, 0x05]).is_err());
+        assert!(decode(&[0xb8, 0x01, 0x00]).is_err());
+        assert!(decode(&[0x80, 0x80]).is_err());
+    }
+}
@@ -0,0 +1,480 @@
+//! Decoding of signed raw transactions.
+//!
+//! `eth_signTransaction` returns the signed transaction as an opaque hex blob.
+//! [`SignedTransaction`] decodes it (legacy, EIP-155, EIP-2930 and EIP-1559),
+//! exposes its fields and hash, and [`check`](SignedTransaction::check)s them
+//! against the [`EvmTransaction`] that was sent for signing, so the raw
+//! transaction can be broadcast knowing the signer did not alter it.
+//!
+//! ```rust,no_run
+//! use bankr_agent_api::{
+//!     BankrAgentClient,
+//!     transaction::SignedTransaction,
+//!     types::{EvmTransaction, SignRequest},
+//! };
+//!
+//! # async fn example(client: &BankrAgentClient) -> Result<(), Box<dyn std::error::Error>> {
+//! let tx = EvmTransaction::new("0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913", 8453);
+//! let resp = client.sign(&SignRequest::transaction(tx.clone())).await?;
+//! let signed: SignedTransaction = resp.signature.as_deref().unwrap_or_default().parse()?;
+//! signed.check(&tx)?;
+//! println!("nonce {} from {}", signed.nonce, signed.recover_sender()?);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{fmt, str::FromStr};
+
+use serde::Serialize;
+
+use crate::{
+    primitives::{Address, Bytes, U256, keccak256},
+    rlp,
+    types::{EvmTransaction, TypedEvmTransaction},
+    verify::{self, VerifyError},
+};
+
+/// The EIP-2718 envelope of a signed transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TxType {
+    /// Untyped legacy transaction, with or without EIP-155 replay protection.
+    Legacy,
+    /// Type `0x01`: access-list transaction.
+    Eip2930,
+    /// Type `0x02`: dynamic-fee transaction.
+    Eip1559,
+}
+
+impl fmt::Display for TxType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Legacy => "legacy",
+            Self::Eip2930 => "eip2930",
+            Self::Eip1559 => "eip1559",
+        })
+    }
+}
+
+/// One entry of an EIP-2930 access list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListItem {
+    /// The account accessed.
+    pub address: Address,
+    /// The 32-byte storage slots accessed.
+    pub storage_keys: Vec<Bytes>,
+}
+
+/// A decoded signed transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedTransaction {
+    /// Envelope type.
+    #[serde(rename = "type")]
+    pub tx_type: TxType,
+    /// Chain ID; `None` for a legacy transaction without EIP-155 replay
+    /// protection.
+    pub chain_id: Option<u64>,
+    /// Sender nonce.
+    pub nonce: u64,
+    /// Recipient; `None` for contract creation.
+    pub to: Option<Address>,
+    /// Value in wei.
+    pub value: U256,
+    /// Calldata.
+    pub data: Bytes,
+    /// Gas limit.
+    pub gas: U256,
+    /// Gas price (legacy and EIP-2930).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_price: Option<U256>,
+    /// Max fee per gas (EIP-1559).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_gas: Option<U256>,
+    /// Max priority fee per gas (EIP-1559).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_priority_fee_per_gas: Option<U256>,
+    /// Access list (empty for legacy transactions).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub access_list: Vec<AccessListItem>,
+    /// `v` as encoded: `27`/`28` or `chain_id * 2 + 35/36` for legacy
+    /// transactions, the y-parity `0`/`1` for typed ones.
+    pub v: u64,
+    /// Signature `r`.
+    pub r: U256,
+    /// Signature `s`.
+    pub s: U256,
+    #[serde(skip)]
+    hash: [u8; 32],
+    #[serde(skip)]
+    signing_hash: [u8; 32],
+}
+
+impl SignedTransaction {
+    /// Decode a signed raw transaction.
+    pub fn decode(raw: &[u8]) -> Result<Self, VerifyError> {
+        decode(raw).map_err(VerifyError::InvalidTransaction)
+    }
+
+    /// The transaction hash: Keccak-256 of the raw signed encoding.
+    pub const fn hash(&self) -> [u8; 32] {
+        self.hash
+    }
+
+    /// The hash the sender signed.
+    pub const fn signing_hash(&self) -> [u8; 32] {
+        self.signing_hash
+    }
+
+    /// The signature's y-parity.
+    pub const fn y_parity(&self) -> bool {
+        match self.tx_type {
+            TxType::Legacy if self.v >= 35 => (self.v - 35) % 2 == 1,
+            TxType::Legacy => self.v == 28,
+            _ => self.v == 1,
+        }
+    }
+
+    /// Recover the sender from the signature.
+    pub fn recover_sender(&self) -> Result<Address, VerifyError> {
+        let mut rs = [0; 64];
+        rs[..32].copy_from_slice(&self.r.to_be_bytes());
+        rs[32..].copy_from_slice(&self.s.to_be_bytes());
+        verify::recover(&self.signing_hash, &rs, self.y_parity())
+    }
+
+    /// Check that this is the transaction `requested` asked to sign.
+    ///
+    /// `to` and `chain_id` must match, and `value` and `data` must match with
+    /// unset meaning zero and empty. Gas, fee and nonce fields are compared
+    /// only when the request sets them, since the signer fills them in
+    /// otherwise.
+    pub fn check(&self, requested: &EvmTransaction) -> Result<(), VerifyError> {
+        let requested = TypedEvmTransaction::try_from(requested)
+            .map_err(|e| VerifyError::InvalidTransaction(format!("requested transaction: {e}")))?;
+
+        compare("to", Some(&requested.to), self.to.as_ref())?;
+        compare("chainId", Some(&requested.chain_id), self.chain_id.as_ref())?;
+        compare("value", Some(&requested.value.unwrap_or_default()), Some(&self.value))?;
+        let data = requested.data.unwrap_or_default();
+        compare("data", Some(&data), Some(&self.data))?;
+
+        if let Some(gas) = &requested.gas {
+            compare("gas", Some(gas), Some(&self.gas))?;
+        }
+        if let Some(gas_price) = &requested.gas_price {
+            compare("gasPrice", Some(gas_price), self.gas_price.as_ref())?;
+        }
+        if let Some(max_fee) = &requested.max_fee_per_gas {
+            compare("maxFeePerGas", Some(max_fee), self.max_fee_per_gas.as_ref())?;
+        }
+        if let Some(priority_fee) = &requested.max_priority_fee_per_gas {
+            compare(
+                "maxPriorityFeePerGas",
+                Some(priority_fee),
+                self.max_priority_fee_per_gas.as_ref(),
+            )?;
+        }
+        if let Some(nonce) = &requested.nonce {
+            compare("nonce", Some(nonce), Some(&self.nonce))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SignedTransaction {
+    type Err = VerifyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let raw: Bytes = s.parse().map_err(|e| VerifyError::InvalidTransaction(format!("{e}")))?;
+        Self::decode(raw.as_slice())
+    }
+}
+
+fn compare<T: PartialEq + fmt::Display>(
+    field: &'static str,
+    requested: Option<&T>,
+    signed: Option<&T>,
+) -> Result<(), VerifyError> {
+    if requested == signed {
+        return Ok(());
+    }
+    let show = |v: Option<&T>| v.map_or_else(|| "(none)".to_owned(), ToString::to_string);
+    Err(VerifyError::FieldMismatch { field, requested: show(requested), signed: show(signed) })
+}
+
+fn decode(raw: &[u8]) -> Result<SignedTransaction, String> {
+    let (tx_type, body) = match raw.first() {
+        Some(0x01) => (TxType::Eip2930, &raw[1..]),
+        Some(0x02) => (TxType::Eip1559, &raw[1..]),
+        Some(0xc0..) => (TxType::Legacy, raw),
+        Some(other) => return Err(format!("unsupported transaction type 0x{other:02x}")),
+        None => return Err("empty transaction".to_owned()),
+    };
+    let item = rlp::decode(body)?;
+    let fields = item.list("transaction")?;
+    let expected = match tx_type {
+        TxType::Legacy => 9,
+        TxType::Eip2930 => 11,
+        TxType::Eip1559 => 12,
+    };
+    if fields.len() != expected {
+        return Err(format!("expected {expected} fields, found {}", fields.len()));
+    }
+    let (unsigned, sig) = fields.split_at(expected - 3);
+    let v = uint(&sig[0], "v")?;
+    let r = word(&sig[1], "r")?;
+    let s = word(&sig[2], "s")?;
+
+    let mut tx = SignedTransaction {
+        tx_type,
+        chain_id: None,
+        nonce: 0,
+        to: None,
+        value: U256::ZERO,
+        data: Bytes::default(),
+        gas: U256::ZERO,
+        gas_price: None,
+        max_fee_per_gas: None,
+        max_priority_fee_per_gas: None,
+        access_list: Vec::new(),
+        v,
+        r,
+        s,
+        hash: keccak256(raw),
+        signing_hash: [0; 32],
+    };
+
+    // The fields after the fee fields are laid out identically in every type.
+    let rest = match tx_type {
+        TxType::Legacy => {
+            tx.nonce = uint(&unsigned[0], "nonce")?;
+            tx.gas_price = Some(word(&unsigned[1], "gasPrice")?);
+            &unsigned[2..]
+        }
+        TxType::Eip2930 => {
+            tx.chain_id = Some(uint(&unsigned[0], "chainId")?);
+            tx.nonce = uint(&unsigned[1], "nonce")?;
+            tx.gas_price = Some(word(&unsigned[2], "gasPrice")?);
+            &unsigned[3..]
+        }
+        TxType::Eip1559 => {
+            tx.chain_id = Some(uint(&unsigned[0], "chainId")?);
+            tx.nonce = uint(&unsigned[1], "nonce")?;
+            tx.max_priority_fee_per_gas = Some(word(&unsigned[2], "maxPriorityFeePerGas")?);
+            tx.max_fee_per_gas = Some(word(&unsigned[3], "maxFeePerGas")?);
+            &unsigned[4..]
+        }
+    };
+    tx.gas = word(&rest[0], "gas")?;
+    tx.to = match rest[1].bytes("to")? {
+        [] => None,
+        bytes => Some(Address::from_bytes(
+            bytes.try_into().map_err(|_| format!("to: expected 20 bytes, got {}", bytes.len()))?,
+        )),
+    };
+    tx.value = word(&rest[2], "value")?;
+    tx.data = Bytes::from(rest[3].bytes("data")?);
+    if let Some(access_list) = rest.get(4) {
+        tx.access_list = decode_access_list(access_list)?;
+    }
+
+    let mut payload: Vec<u8> = unsigned.iter().flat_map(|f| f.raw.iter().copied()).collect();
+    let preimage = match (tx_type, v) {
+        (TxType::Legacy, 27 | 28) => rlp::encode_list(&payload),
+        (TxType::Legacy, 35..) => {
+            // EIP-155: v = chain_id * 2 + 35 + parity.
+            let chain_id = (v - 35) / 2;
+            tx.chain_id = Some(chain_id);
+            rlp::encode_uint(&mut payload, chain_id);
+            payload.extend_from_slice(&[0x80, 0x80]);
+            rlp::encode_list(&payload)
+        }
+        (TxType::Legacy, _) => return Err(format!("invalid v {v}")),
+        (_, 0 | 1) => {
+            let mut preimage = vec![raw[0]];
+            preimage.extend_from_slice(&rlp::encode_list(&payload));
+            preimage
+        }
+        (_, _) => return Err(format!("invalid y-parity {v}")),
+    };
+    tx.signing_hash = keccak256(&preimage);
+    Ok(tx)
+}
+
+fn decode_access_list(item: &rlp::Item<'_>) -> Result<Vec<AccessListItem>, String> {
+    item.list("accessList")?
+        .iter()
+        .map(|entry| {
+            let [address, keys] = entry.list("accessList entry")? else {
+                return Err("accessList entry: expected [address, storageKeys]".to_owned());
+            };
+            let address = address.bytes("accessList address")?;
+            let address = address.try_into().map_err(|_| {
+                format!("accessList address: expected 20 bytes, got {}", address.len())
+            })?;
+            let storage_keys = keys
+                .list("storageKeys")?
+                .iter()
+                .map(|key| match key.bytes("storageKey")? {
+                    key if key.len() == 32 => Ok(Bytes::from(key)),
+                    key => Err(format!("storageKey: expected 32 bytes, got {}", key.len())),
+                })
+                .collect::<Result<_, _>>()?;
+            Ok(AccessListItem { address: Address::from_bytes(address), storage_keys })
+        })
+        .collect()
+}
+
+/// Read a canonical big-endian integer field of at most 32 bytes.
+fn word(item: &rlp::Item<'_>, field: &str) -> Result<U256, String> {
+    U256::from_be_slice(integer(item, field)?).ok_or_else(|| format!("{field}: value too large"))
+}
+
+/// Read a canonical big-endian integer field of at most 8 bytes.
+fn uint(item: &rlp::Item<'_>, field: &str) -> Result<u64, String> {
+    let bytes = integer(item, field)?;
+    if bytes.len() > 8 {
+        return Err(format!("{field}: value too large"));
+    }
+    Ok(bytes.iter().fold(0, |acc, b| (acc << 8) | u64::from(*b)))
+}
+
+fn integer<'a>(item: &rlp::Item<'a>, field: &str) -> Result<&'a [u8], String> {
+    let bytes = item.bytes(field)?;
+    if bytes.first() == Some(&0) {
+        return Err(format!("{field}: integer with leading zero"));
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use k256::ecdsa::SigningKey;
+
+    use super::*;
+    use crate::primitives::decode_hex;
+
+    fn hex(s: &str) -> Vec<u8> {
+        decode_hex(s.trim_start_matches("0x")).unwrap_or_default()
+    }
+
+    /// The signed transaction from the EIP-155 specification.
+    const EIP155_TX: &str = "0xf86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a7640000\
+         8025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f76\
+         1aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+
+    /// Build and sign an EIP-1559 transaction on chain 8453.
+    fn eip1559(key: &SigningKey) -> Result<Vec<u8>, k256::ecdsa::Error> {
+        let mut payload = Vec::new();
+        for value in [8453u64, 7, 1_000_000_000, 2_000_000_000, 21_000] {
+            rlp::encode_uint(&mut payload, value);
+        }
+        rlp::encode_bytes(&mut payload, &[0x35; 20]);
+        rlp::encode_uint(&mut payload, 1);
+        rlp::encode_bytes(&mut payload, &[0xab, 0xcd]);
+        let mut entry = Vec::new();
+        rlp::encode_bytes(&mut entry, &[0x11; 20]);
+        let mut keys = Vec::new();
+        rlp::encode_bytes(&mut keys, &[0x22; 32]);
+        entry.extend_from_slice(&rlp::encode_list(&keys));
+        payload.extend_from_slice(&rlp::encode_list(&rlp::encode_list(&entry)));
+
+        let mut unsigned = vec![0x02];
+        unsigned.extend_from_slice(&rlp::encode_list(&payload));
+        let (signature, recovery_id) = key.sign_prehash_recoverable(&keccak256(&unsigned))?;
+        let signature = signature.to_bytes();
+
+        rlp::encode_uint(&mut payload, u64::from(recovery_id.to_byte()));
+        rlp::encode_bytes(&mut payload, &signature[..32]);
+        rlp::encode_bytes(&mut payload, &signature[32..]);
+        let mut raw = vec![0x02];
+        raw.extend_from_slice(&rlp::encode_list(&payload));
+        Ok(raw)
+    }
+
+    #[test]
+    fn decodes_eip155_transaction() -> Result<(), VerifyError> {
+        let tx: SignedTransaction = EIP155_TX.parse()?;
+        assert_eq!(tx.tx_type, TxType::Legacy);
+        assert_eq!(tx.chain_id, Some(1));
+        assert_eq!(tx.nonce, 9);
+        assert_eq!(tx.gas_price, Some(U256::from(20_000_000_000u64)));
+        assert_eq!(tx.gas, U256::from(21_000u64));
+        assert_eq!(tx.to, Some(Address::from_bytes([0x35; 20])));
+        assert_eq!(tx.value, U256::from(1_000_000_000_000_000_000u64));
+        assert!(tx.data.is_empty());
+        assert_eq!(tx.v, 37);
+        assert_eq!(
+            tx.signing_hash().to_vec(),
+            hex("0xdaf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53")
+        );
+        assert_eq!(tx.hash(), keccak256(&hex(EIP155_TX)));
+        assert_eq!(tx.recover_sender()?.to_string(), "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F");
+        Ok(())
+    }
+
+    #[test]
+    fn decodes_eip1559_transaction() -> Result<(), Box<dyn std::error::Error>> {
+        let key = SigningKey::from_slice(&[0x46; 32])?;
+        let tx = SignedTransaction::decode(&eip1559(&key)?)?;
+        assert_eq!(tx.tx_type, TxType::Eip1559);
+        assert_eq!(tx.chain_id, Some(8453));
+        assert_eq!(tx.nonce, 7);
+        assert_eq!(tx.max_priority_fee_per_gas, Some(U256::from(1_000_000_000u64)));
+        assert_eq!(tx.max_fee_per_gas, Some(U256::from(2_000_000_000u64)));
+        assert_eq!(tx.gas_price, None);
+        assert_eq!(tx.data.as_slice(), [0xab, 0xcd]);
+        assert_eq!(tx.access_list.len(), 1);
+        assert_eq!(tx.access_list[0].storage_keys, [Bytes::from(&[0x22; 32][..])]);
+
+        let sender = tx.recover_sender()?;
+        let point = key.verifying_key().to_encoded_point(false);
+        assert_eq!(sender.as_bytes()[..], keccak256(&point.as_bytes()[1..])[12..]);
+        Ok(())
+    }
+
+    #[test]
+    fn check_against_request() -> Result<(), Box<dyn std::error::Error>> {
+        let key = SigningKey::from_slice(&[0x46; 32])?;
+        let tx = SignedTransaction::decode(&eip1559(&key)?)?;
+        let requested = EvmTransaction {
+            value: Some("1".to_owned()),
+            data: Some("0xABCD".to_owned()),
+            nonce: Some(7),
+            ..EvmTransaction::new(Address::from_bytes([0x35; 20]).to_string(), 8453)
+        };
+        tx.check(&requested)?;
+
+        let other_chain = EvmTransaction { chain_id: 1, ..requested.clone() };
+        assert!(matches!(
+            tx.check(&other_chain),
+            Err(VerifyError::FieldMismatch { field: "chainId", .. })
+        ));
+        let no_value = EvmTransaction { value: None, ..requested.clone() };
+        assert!(matches!(
+            tx.check(&no_value),
+            Err(VerifyError::FieldMismatch { field: "value", .. })
+        ));
+        let legacy_fee = EvmTransaction { gas_price: Some("1".to_owned()), ..requested };
+        assert_eq!(
+            tx.check(&legacy_fee),
+            Err(VerifyError::FieldMismatch {
+                field: "gasPrice",
+                requested: "1".to_owned(),
+                signed: "(none)".to_owned(),
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_malformed_transactions() {
+        let raw = hex(EIP155_TX);
+        assert!(SignedTransaction::decode(&raw[..raw.len() - 1]).is_err());
+        assert!(SignedTransaction::decode(&[0x03, 0xc0]).is_err());
+        assert!(SignedTransaction::decode(&[0x02, 0xc0]).is_err());
+        assert!(SignedTransaction::decode(&[]).is_err());
+    }
+}
@@ -5,6 +5,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::primitives::{Address, Bytes, PrimitiveError, U256};
+
 // ---------------------------------------------------------------------------
 // User Info — GET /agent/me
 // ---------------------------------------------------------------------------
@@ -18,6 +20,14 @@ pub struct Wallet {
     pub address: String,
 }
 
+impl Wallet {
+    /// The wallet address as an [`Address`]. `None` for non-EVM wallets or
+    /// a malformed address.
+    pub fn evm_address(&self) -> Option<Address> {
+        if self.chain == "evm" { self.address.parse().ok() } else { None }
+    }
+}
+
 /// Social account linked to the user.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SocialAccount {
@@ -117,6 +127,13 @@ pub enum JobStatus {
     Cancelled,
 }
 
+impl JobStatus {
+    /// Whether the job has finished: completed, failed or cancelled.
+    pub const fn is_terminal(&self) -> bool {
+        matches!(self, Self::Completed | Self::Failed | Self::Cancelled)
+    }
+}
+
 impl std::fmt::Display for JobStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -138,15 +155,327 @@ pub struct StatusUpdate {
     pub timestamp: Option<String>,
 }
 
+/// Market data for a token (`"type": "token_info"`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenInfo {
+    /// Token ticker (e.g. `"ETH"`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    /// Token name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Contract address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    /// Chain name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain: Option<String>,
+    /// Price in USD.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<f64>,
+    /// 24-hour price change in percent.
+    #[serde(rename = "change24h", skip_serializing_if = "Option::is_none")]
+    pub change_24h: Option<f64>,
+    /// Market capitalisation in USD.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub market_cap: Option<f64>,
+    /// 24-hour trading volume in USD.
+    #[serde(rename = "volume24h", skip_serializing_if = "Option::is_none")]
+    pub volume_24h: Option<f64>,
+    /// Fields not modelled above.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// One point of a chart series.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChartPoint {
+    /// Unix timestamp in milliseconds.
+    pub timestamp: u64,
+    /// Value at `timestamp`.
+    pub value: f64,
+}
+
+/// A price chart (`"type": "chart"`), as an image URL and/or a data series.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Chart {
+    /// Chart image URL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Token ticker the chart is for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    /// Time range covered (e.g. `"24h"`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeframe: Option<String>,
+    /// Data points, oldest first.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub series: Option<Vec<ChartPoint>>,
+    /// Fields not modelled above.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
 /// Rich data item returned with completed jobs.
+///
+/// Kinds not listed here, or whose payload does not match the modelled
+/// shape, are kept verbatim in [`RichDataItem::Other`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct RichDataItem {
-    /// Type discriminator (e.g. `"token_info"`, `"chart"`).
-    #[serde(rename = "type")]
-    pub kind: String,
-    /// Remaining fields vary by type.
+#[serde(tag = "type")]
+pub enum RichDataItem {
+    /// Token market data.
+    #[serde(rename = "token_info")]
+    TokenInfo(TokenInfo),
+    /// Price chart.
+    #[serde(rename = "chart")]
+    Chart(Chart),
+    /// Any other item, kept as the raw JSON object.
+    #[serde(untagged)]
+    Other(serde_json::Value),
+}
+
+impl RichDataItem {
+    /// The `type` discriminator as sent by the API.
+    pub fn kind(&self) -> &str {
+        match self {
+            Self::TokenInfo(_) => "token_info",
+            Self::Chart(_) => "chart",
+            Self::Other(v) => {
+                v.get("type").and_then(serde_json::Value::as_str).unwrap_or("unknown")
+            }
+        }
+    }
+}
+
+/// Details of the user's intent attached to a job transaction
+/// (`metadata.__ORIGINAL_TX_DATA__`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OriginalTxData {
+    /// Chain name (e.g. `"base"`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain: Option<String>,
+    /// Human-readable summary of the transaction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub human_readable_message: Option<String>,
+    /// Input token contract address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_token_address: Option<String>,
+    /// Input token amount in base units.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_token_amount: Option<String>,
+    /// Input token ticker.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_token_ticker: Option<String>,
+    /// Output token contract address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_token_address: Option<String>,
+    /// Output token ticker.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_token_ticker: Option<String>,
+    /// Recipient address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receiver: Option<String>,
+    /// Fields not modelled above.
     #[serde(flatten)]
-    pub extra: serde_json::Value,
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Metadata of a job transaction.
+///
+/// Most types wrap the intent in `__ORIGINAL_TX_DATA__` and the calldata in
+/// `transaction`; others (`swapCrossChain`, `avantisTrade`,
+/// `manage_bankr_staking`) put `chainId`, `to`, `data` and `value` directly
+/// in the metadata alongside a `description`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionMetadata {
+    /// What the transaction does, in user terms.
+    #[serde(rename = "__ORIGINAL_TX_DATA__", skip_serializing_if = "Option::is_none")]
+    pub original_tx_data: Option<OriginalTxData>,
+    /// The transaction to execute.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction: Option<EvmTransaction>,
+    /// Human-readable description (flat form).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Chain ID (flat form).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<u64>,
+    /// Destination address (flat form).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    /// Calldata (flat form).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+    /// Value in wei (flat form).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// Gas limit (flat form).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas: Option<String>,
+    /// Legacy gas price in wei (flat form).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_price: Option<String>,
+    /// Fields not modelled above.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl TransactionMetadata {
+    /// The executable transaction, from either the nested or the flat form.
+    pub fn evm_transaction(&self) -> Option<EvmTransaction> {
+        if let Some(tx) = &self.transaction {
+            return Some(tx.clone());
+        }
+        Some(EvmTransaction {
+            to: self.to.clone()?,
+            chain_id: self.chain_id?,
+            value: self.value.clone(),
+            data: self.data.clone(),
+            gas: self.gas.clone(),
+            gas_price: self.gas_price.clone(),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            nonce: None,
+        })
+    }
+
+    /// Human-readable summary, if the API provided one.
+    pub fn summary(&self) -> Option<&str> {
+        self.original_tx_data
+            .as_ref()
+            .and_then(|d| d.human_readable_message.as_deref())
+            .or(self.description.as_deref())
+    }
+}
+
+/// A transaction prepared by the agent, returned in the `transactions` array
+/// of completed jobs.
+///
+/// Types not listed here, or whose metadata does not match the documented
+/// shape, are kept verbatim in [`JobTransaction::Unknown`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "metadata")]
+pub enum JobTransaction {
+    /// Token-to-token or token-to-native swap.
+    #[serde(rename = "swap")]
+    Swap(TransactionMetadata),
+    /// Cross-chain swap via a bridge aggregator.
+    #[serde(rename = "swapCrossChain")]
+    SwapCrossChain(TransactionMetadata),
+    /// ERC-20 token transfer.
+    #[serde(rename = "transfer_erc20")]
+    TransferErc20(TransactionMetadata),
+    /// Native ETH transfer.
+    #[serde(rename = "transfer_eth")]
+    TransferEth(TransactionMetadata),
+    /// Token approval.
+    #[serde(rename = "approval")]
+    Approval(TransactionMetadata),
+    /// Wrap ETH to WETH.
+    #[serde(rename = "convert_eth_to_weth")]
+    ConvertEthToWeth(TransactionMetadata),
+    /// Unwrap WETH to ETH.
+    #[serde(rename = "convert_weth_to_eth")]
+    ConvertWethToEth(TransactionMetadata),
+    /// Purchase an NFT from a marketplace.
+    #[serde(rename = "buy_nft")]
+    BuyNft(TransactionMetadata),
+    /// Transfer an NFT.
+    #[serde(rename = "transfer_nft")]
+    TransferNft(TransactionMetadata),
+    /// Mint an NFT from a Manifold contract.
+    #[serde(rename = "mint_manifold_nft")]
+    MintManifoldNft(TransactionMetadata),
+    /// Mint an NFT from a SeaDrop contract.
+    #[serde(rename = "mint_seadrop_nft")]
+    MintSeadropNft(TransactionMetadata),
+    /// Leveraged trading position on Avantis.
+    #[serde(rename = "avantisTrade")]
+    AvantisTrade(TransactionMetadata),
+    /// Stake or unstake BANKR.
+    #[serde(rename = "manage_bankr_staking")]
+    ManageBankrStaking(TransactionMetadata),
+    /// Any other transaction, kept as the raw JSON object.
+    #[serde(untagged)]
+    Unknown(serde_json::Value),
+}
+
+impl JobTransaction {
+    /// The `type` discriminator as sent by the API.
+    pub fn kind(&self) -> &str {
+        match self {
+            Self::Swap(_) => "swap",
+            Self::SwapCrossChain(_) => "swapCrossChain",
+            Self::TransferErc20(_) => "transfer_erc20",
+            Self::TransferEth(_) => "transfer_eth",
+            Self::Approval(_) => "approval",
+            Self::ConvertEthToWeth(_) => "convert_eth_to_weth",
+            Self::ConvertWethToEth(_) => "convert_weth_to_eth",
+            Self::BuyNft(_) => "buy_nft",
+            Self::TransferNft(_) => "transfer_nft",
+            Self::MintManifoldNft(_) => "mint_manifold_nft",
+            Self::MintSeadropNft(_) => "mint_seadrop_nft",
+            Self::AvantisTrade(_) => "avantisTrade",
+            Self::ManageBankrStaking(_) => "manage_bankr_staking",
+            Self::Unknown(v) => {
+                v.get("type").and_then(serde_json::Value::as_str).unwrap_or("unknown")
+            }
+        }
+    }
+
+    /// The transaction metadata. `None` for [`JobTransaction::Unknown`].
+    pub const fn metadata(&self) -> Option<&TransactionMetadata> {
+        match self {
+            Self::Swap(m) |
+            Self::SwapCrossChain(m) |
+            Self::TransferErc20(m) |
+            Self::TransferEth(m) |
+            Self::Approval(m) |
+            Self::ConvertEthToWeth(m) |
+            Self::ConvertWethToEth(m) |
+            Self::BuyNft(m) |
+            Self::TransferNft(m) |
+            Self::MintManifoldNft(m) |
+            Self::MintSeadropNft(m) |
+            Self::AvantisTrade(m) |
+            Self::ManageBankrStaking(m) => Some(m),
+            Self::Unknown(_) => None,
+        }
+    }
+
+    /// The executable transaction, ready for
+    /// [`sign`](crate::BankrAgentClient::sign) or
+    /// [`submit_transaction`](crate::BankrAgentClient::submit_transaction).
+    ///
+    /// For [`JobTransaction::Unknown`] the `metadata` object is parsed on a
+    /// best-effort basis.
+    pub fn evm_transaction(&self) -> Option<EvmTransaction> {
+        match self {
+            Self::Unknown(v) => {
+                serde_json::from_value::<TransactionMetadata>(v.get("metadata")?.clone())
+                    .ok()?
+                    .evm_transaction()
+            }
+            _ => self.metadata()?.evm_transaction(),
+        }
+    }
+}
+
+impl TryFrom<&JobTransaction> for EvmTransaction {
+    type Error = crate::error::BankrError;
+
+    fn try_from(tx: &JobTransaction) -> Result<Self, Self::Error> {
+        tx.evm_transaction().ok_or_else(|| {
+            crate::error::BankrError::Deserialization(format!(
+                "{} transaction has no executable `to`/`chainId`",
+                tx.kind()
+            ))
+        })
+    }
 }
 
 /// Response from `GET /agent/job/{jobId}`.
@@ -183,6 +512,50 @@ pub struct JobResponse {
     pub error: Option<String>,
     /// When the job was cancelled.
     pub cancelled_at: Option<String>,
+    /// Transactions prepared by the agent (when completed).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transactions: Option<Vec<JobTransaction>>,
+}
+
+impl JobResponse {
+    /// A job with the given ID and status and no other data, e.g. for
+    /// scripting [`MockBankrApi`](crate::mock::MockBankrApi) responses.
+    pub fn new(job_id: &str, status: JobStatus) -> Self {
+        Self {
+            success: true,
+            job_id: job_id.to_owned(),
+            thread_id: None,
+            status,
+            prompt: String::new(),
+            created_at: String::new(),
+            cancellable: None,
+            status_updates: None,
+            started_at: None,
+            response: None,
+            rich_data: None,
+            completed_at: None,
+            processing_time: None,
+            error: None,
+            cancelled_at: None,
+            transactions: None,
+        }
+    }
+
+    /// Token market data in [`rich_data`](Self::rich_data).
+    pub fn token_infos(&self) -> impl Iterator<Item = &TokenInfo> {
+        self.rich_data.iter().flatten().filter_map(|item| match item {
+            RichDataItem::TokenInfo(info) => Some(info),
+            _ => None,
+        })
+    }
+
+    /// Charts in [`rich_data`](Self::rich_data).
+    pub fn charts(&self) -> impl Iterator<Item = &Chart> {
+        self.rich_data.iter().flatten().filter_map(|item| match item {
+            RichDataItem::Chart(chart) => Some(chart),
+            _ => None,
+        })
+    }
 }
 
 /// Response from `POST /agent/job/{jobId}/cancel`.
@@ -262,26 +635,358 @@ pub struct EvmTransaction {
     pub nonce: Option<u64>,
 }
 
-/// Request body for `POST /agent/sign`.
+impl EvmTransaction {
+    /// A transaction to `to` on `chain_id` with all optional fields unset.
+    pub fn new(to: impl Into<String>, chain_id: u64) -> Self {
+        Self {
+            to: to.into(),
+            chain_id,
+            value: None,
+            data: None,
+            gas: None,
+            gas_price: None,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            nonce: None,
+        }
+    }
+
+    /// Parse every field into its typed form, rejecting malformed addresses,
+    /// amounts and calldata.
+    pub fn validate(&self) -> Result<TypedEvmTransaction, PrimitiveError> {
+        TypedEvmTransaction::try_from(self)
+    }
+}
+
+/// [`EvmTransaction`] with validated field types.
 ///
-/// The body shape depends on `signature_type`:
-/// - `personal_sign` → `message` is required
-/// - `eth_signTypedData_v4` → `typed_data` is required
-/// - `eth_signTransaction` → `transaction` is required
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Serializes to the same JSON as [`EvmTransaction`]; convert with `From` /
+/// `TryFrom` to pass it to APIs that take the string form.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct SignRequest {
-    /// The type of signature to produce.
-    pub signature_type: SignatureType,
-    /// Plain text message (for `personal_sign`).
+pub struct TypedEvmTransaction {
+    /// Destination address.
+    pub to: Address,
+    /// Chain ID.
+    pub chain_id: u64,
+    /// Value in wei.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub message: Option<String>,
-    /// EIP-712 typed data (for `eth_signTypedData_v4`).
+    pub value: Option<U256>,
+    /// Calldata.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Bytes>,
+    /// Gas limit.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub typed_data: Option<serde_json::Value>,
-    /// Transaction to sign (for `eth_signTransaction`).
+    pub gas: Option<U256>,
+    /// Legacy gas price in wei.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub transaction: Option<EvmTransaction>,
+    pub gas_price: Option<U256>,
+    /// EIP-1559 max fee per gas.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_gas: Option<U256>,
+    /// EIP-1559 priority fee.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_priority_fee_per_gas: Option<U256>,
+    /// Transaction nonce.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
+}
+
+impl TypedEvmTransaction {
+    /// A transaction to `to` on `chain_id` with all optional fields unset.
+    pub const fn new(to: Address, chain_id: u64) -> Self {
+        Self {
+            to,
+            chain_id,
+            value: None,
+            data: None,
+            gas: None,
+            gas_price: None,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            nonce: None,
+        }
+    }
+}
+
+impl TryFrom<&EvmTransaction> for TypedEvmTransaction {
+    type Error = PrimitiveError;
+
+    fn try_from(tx: &EvmTransaction) -> Result<Self, Self::Error> {
+        fn parse<T: std::str::FromStr>(s: Option<&String>) -> Result<Option<T>, T::Err> {
+            s.map(|s| s.parse()).transpose()
+        }
+
+        Ok(Self {
+            to: tx.to.parse()?,
+            chain_id: tx.chain_id,
+            value: parse(tx.value.as_ref())?,
+            data: parse(tx.data.as_ref())?,
+            gas: parse(tx.gas.as_ref())?,
+            gas_price: parse(tx.gas_price.as_ref())?,
+            max_fee_per_gas: parse(tx.max_fee_per_gas.as_ref())?,
+            max_priority_fee_per_gas: parse(tx.max_priority_fee_per_gas.as_ref())?,
+            nonce: tx.nonce,
+        })
+    }
+}
+
+impl From<TypedEvmTransaction> for EvmTransaction {
+    fn from(tx: TypedEvmTransaction) -> Self {
+        Self {
+            to: tx.to.into(),
+            chain_id: tx.chain_id,
+            value: tx.value.map(Into::into),
+            data: tx.data.map(Into::into),
+            gas: tx.gas.map(Into::into),
+            gas_price: tx.gas_price.map(Into::into),
+            max_fee_per_gas: tx.max_fee_per_gas.map(Into::into),
+            max_priority_fee_per_gas: tx.max_priority_fee_per_gas.map(Into::into),
+            nonce: tx.nonce,
+        }
+    }
+}
+
+/// A field of an EIP-712 struct type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypedDataField {
+    /// Field name.
+    pub name: String,
+    /// Solidity type (e.g. `"address"`, `"uint256"`, `"Person[]"`).
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+/// The EIP-712 domain separator fields.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Eip712Domain {
+    /// Signing domain name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Signing domain version.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Chain ID. Accepted as a number, a decimal string (`"8453"`) or a hex
+    /// string (`"0x2105"`); always serialized as a number.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_chain_id"
+    )]
+    pub chain_id: Option<u64>,
+    /// Address of the verifying contract.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verifying_contract: Option<String>,
+    /// Disambiguating salt (32-byte hex).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub salt: Option<String>,
+    /// Fields not modelled above.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A chain ID given as a number or as a decimal or `0x` hex string, as
+/// wallets and dapps send all three.
+fn deserialize_chain_id<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<u64>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum ChainId {
+        Number(u64),
+        Text(String),
+    }
+
+    let text = match Option::<ChainId>::deserialize(deserializer)? {
+        None => return Ok(None),
+        Some(ChainId::Number(id)) => return Ok(Some(id)),
+        Some(ChainId::Text(text)) => text,
+    };
+    let trimmed = text.trim();
+    let parsed = match trimmed.strip_prefix("0x").or_else(|| trimmed.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => trimmed.parse(),
+    };
+    parsed.map(Some).map_err(|_| serde::de::Error::custom(format!("invalid chainId `{text}`")))
+}
+
+/// EIP-712 typed data, as passed to `eth_signTypedData_v4`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedData {
+    /// Struct type definitions, keyed by type name. May include
+    /// `EIP712Domain`.
+    pub types: std::collections::BTreeMap<String, Vec<TypedDataField>>,
+    /// Name of the type of `message`.
+    pub primary_type: String,
+    /// Domain separator fields.
+    pub domain: Eip712Domain,
+    /// The message to sign.
+    pub message: serde_json::Value,
+}
+
+/// A `personal_sign` message.
+///
+/// On the wire both forms are a string: text as-is, bytes as `0x` hex. When
+/// deserializing, a valid `0x` hex string is read as [`PersonalMessage::Bytes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PersonalMessage {
+    /// UTF-8 text.
+    Text(String),
+    /// Raw bytes.
+    Bytes(Bytes),
+}
+
+impl PersonalMessage {
+    /// The bytes that are signed.
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            Self::Text(text) => text.as_bytes(),
+            Self::Bytes(bytes) => bytes.as_slice(),
+        }
+    }
+}
+
+impl Serialize for PersonalMessage {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Text(text) => serializer.serialize_str(text),
+            Self::Bytes(bytes) => bytes.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PersonalMessage {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        Ok(text.parse().map_or(Self::Text(text), Self::Bytes))
+    }
+}
+
+impl From<String> for PersonalMessage {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<&str> for PersonalMessage {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_owned())
+    }
+}
+
+impl From<Bytes> for PersonalMessage {
+    fn from(bytes: Bytes) -> Self {
+        Self::Bytes(bytes)
+    }
+}
+
+impl From<Vec<u8>> for PersonalMessage {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::Bytes(bytes.into())
+    }
+}
+
+/// What to sign, one variant per [`SignatureType`].
+#[derive(Debug, Clone)]
+pub enum SignPayload {
+    /// `personal_sign` a message.
+    PersonalSign(PersonalMessage),
+    /// `eth_signTypedData_v4` EIP-712 typed data.
+    TypedDataV4(TypedData),
+    /// `eth_signTransaction` a transaction without broadcasting.
+    Transaction(EvmTransaction),
+}
+
+impl SignPayload {
+    /// The signature type this payload is sent as.
+    pub const fn signature_type(&self) -> SignatureType {
+        match self {
+            Self::PersonalSign(_) => SignatureType::PersonalSign,
+            Self::TypedDataV4(_) => SignatureType::EthSignTypedDataV4,
+            Self::Transaction(_) => SignatureType::EthSignTransaction,
+        }
+    }
+}
+
+/// Request body for `POST /agent/sign`.
+///
+/// Serializes to `signatureType` plus exactly the payload field it requires
+/// (`message`, `typedData` or `transaction`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "WireSignRequest", try_from = "WireSignRequest")]
+pub struct SignRequest {
+    /// What to sign.
+    pub payload: SignPayload,
+}
+
+impl SignRequest {
+    /// `personal_sign` a text or byte message.
+    pub fn personal(message: impl Into<PersonalMessage>) -> Self {
+        Self { payload: SignPayload::PersonalSign(message.into()) }
+    }
+
+    /// `eth_signTypedData_v4` EIP-712 typed data.
+    pub const fn typed_data(typed_data: TypedData) -> Self {
+        Self { payload: SignPayload::TypedDataV4(typed_data) }
+    }
+
+    /// `eth_signTransaction` a transaction without broadcasting.
+    pub const fn transaction(transaction: EvmTransaction) -> Self {
+        Self { payload: SignPayload::Transaction(transaction) }
+    }
+
+    /// The signature type this request is sent as.
+    pub const fn signature_type(&self) -> SignatureType {
+        self.payload.signature_type()
+    }
+}
+
+impl From<SignPayload> for SignRequest {
+    fn from(payload: SignPayload) -> Self {
+        Self { payload }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WireSignRequest {
+    signature_type: SignatureType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    message: Option<PersonalMessage>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    typed_data: Option<TypedData>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    transaction: Option<EvmTransaction>,
+}
+
+impl From<SignRequest> for WireSignRequest {
+    fn from(req: SignRequest) -> Self {
+        let signature_type = req.signature_type();
+        let mut wire = Self { signature_type, message: None, typed_data: None, transaction: None };
+        match req.payload {
+            SignPayload::PersonalSign(message) => wire.message = Some(message),
+            SignPayload::TypedDataV4(typed_data) => wire.typed_data = Some(typed_data),
+            SignPayload::Transaction(transaction) => wire.transaction = Some(transaction),
+        }
+        wire
+    }
+}
+
+impl TryFrom<WireSignRequest> for SignRequest {
+    type Error = String;
+
+    fn try_from(wire: WireSignRequest) -> Result<Self, Self::Error> {
+        let payload = match wire.signature_type {
+            SignatureType::PersonalSign => wire.message.map(SignPayload::PersonalSign),
+            SignatureType::EthSignTypedDataV4 => wire.typed_data.map(SignPayload::TypedDataV4),
+            SignatureType::EthSignTransaction => wire.transaction.map(SignPayload::Transaction),
+        };
+        payload
+            .map(Self::from)
+            .ok_or_else(|| format!("{} request is missing its payload field", wire.signature_type))
+    }
 }
 
 /// Success response from `POST /agent/sign`.
@@ -300,6 +1005,13 @@ pub struct SignResponse {
     pub error: Option<String>,
 }
 
+impl SignResponse {
+    /// The signer as an [`Address`]. `None` if absent or malformed.
+    pub fn signer_address(&self) -> Option<Address> {
+        self.signer.as_deref().and_then(|s| s.parse().ok())
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Submit — POST /agent/submit
 // ---------------------------------------------------------------------------
@@ -345,7 +1057,7 @@ pub struct SubmitResponse {
 // ---------------------------------------------------------------------------
 
 /// Standard error envelope returned by the Bankr API.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiErrorBody {
     /// Error type/title.
@@ -359,3 +1071,203 @@ pub struct ApiErrorBody {
     /// Number of messages used in the current window.
     pub used: Option<u64>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRANSACTIONS: &str = r#"[
+        {
+            "type": "swap",
+            "metadata": {
+                "__ORIGINAL_TX_DATA__": {
+                    "chain": "base",
+                    "humanReadableMessage": "Swap 0.1 ETH for USDC on Base",
+                    "inputTokenAddress": "0x0000000000000000000000000000000000000000",
+                    "inputTokenAmount": "100000000000000000",
+                    "inputTokenTicker": "ETH",
+                    "outputTokenAddress": "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913",
+                    "outputTokenTicker": "USDC",
+                    "receiver": "0x1234567890abcdef1234567890abcdef12345678"
+                },
+                "transaction": {
+                    "chainId": 8453,
+                    "to": "0x3fC91A3afd70395Cd496C647d5a6CC9D4B2b7FAD",
+                    "data": "0x3593564c",
+                    "gas": "250000",
+                    "gasPrice": "1000000000",
+                    "value": "100000000000000000"
+                }
+            }
+        },
+        {
+            "type": "avantisTrade",
+            "metadata": {
+                "chainId": 8453,
+                "description": "Open 10x long on BTC with 100 USDC",
+                "to": "0x5FF292d70bA9cD9e7CCb313782811b3D7120535f",
+                "data": "0x...",
+                "value": "0"
+            }
+        },
+        { "type": "bridge_v2", "metadata": { "chainId": 1, "to": "0xabc" } }
+    ]"#;
+
+    #[test]
+    fn transactions_parse_and_convert() -> Result<(), serde_json::Error> {
+        let txs: Vec<JobTransaction> = serde_json::from_str(TRANSACTIONS)?;
+        assert!(matches!(txs[0], JobTransaction::Swap(_)));
+        assert!(matches!(txs[1], JobTransaction::AvantisTrade(_)));
+        assert!(matches!(txs[2], JobTransaction::Unknown(_)));
+        assert_eq!(txs[2].kind(), "bridge_v2");
+
+        let swap = txs[0].metadata().and_then(|m| m.original_tx_data.as_ref());
+        assert_eq!(swap.and_then(|d| d.output_token_ticker.as_deref()), Some("USDC"));
+        assert_eq!(
+            txs[1].metadata().and_then(TransactionMetadata::summary),
+            Some("Open 10x long on BTC with 100 USDC")
+        );
+
+        let evm: Vec<_> = txs.iter().filter_map(JobTransaction::evm_transaction).collect();
+        assert_eq!(evm.len(), 3);
+        assert_eq!(evm[0].gas_price.as_deref(), Some("1000000000"));
+        assert_eq!(evm[1].to, "0x5FF292d70bA9cD9e7CCb313782811b3D7120535f");
+        assert_eq!(evm[2].chain_id, 1);
+
+        let round_trip = serde_json::to_value(&txs)?;
+        assert_eq!(round_trip, serde_json::from_str::<serde_json::Value>(TRANSACTIONS)?);
+        Ok(())
+    }
+
+    #[test]
+    fn rich_data_is_typed_and_lossless() -> Result<(), serde_json::Error> {
+        let json = serde_json::json!([
+            { "type": "token_info", "symbol": "ETH", "price": 3245.67, "change24h": 2.3,
+              "marketCap": 390_000_000_000.0_f64, "volume24h": 12_000_000_000.0_f64, "rank": 2 },
+            { "type": "chart", "url": "https://example.com/eth.png",
+              "series": [{ "timestamp": 1_705_314_600_000_u64, "value": 3245.67 }] },
+            { "type": "chart", "series": "not-a-series" },
+            { "type": "news", "headline": "ETH ETF approved" }
+        ]);
+        let items: Vec<RichDataItem> = serde_json::from_value(json.clone())?;
+        let kinds: Vec<_> = items.iter().map(RichDataItem::kind).collect();
+        assert_eq!(kinds, ["token_info", "chart", "chart", "news"]);
+        assert!(matches!(items[2], RichDataItem::Other(_)));
+        assert_eq!(serde_json::to_value(&items)?, json);
+
+        let job = JobResponse {
+            rich_data: Some(items),
+            ..JobResponse::new("job_1", JobStatus::Completed)
+        };
+        let info: Vec<_> = job.token_infos().collect();
+        assert_eq!(info.len(), 1);
+        assert_eq!(info[0].symbol.as_deref(), Some("ETH"));
+        assert_eq!(info[0].volume_24h, Some(12_000_000_000.0));
+        assert_eq!(info[0].extra.get("rank"), Some(&serde_json::json!(2)));
+        let charts: Vec<_> = job.charts().collect();
+        assert_eq!(charts.len(), 1);
+        assert_eq!(charts[0].series.as_ref().map(Vec::len), Some(1));
+        Ok(())
+    }
+
+    #[test]
+    fn typed_transaction_matches_string_form() -> Result<(), PrimitiveError> {
+        let tx = EvmTransaction {
+            to: "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913".to_owned(),
+            chain_id: 8453,
+            value: Some("1000000000000000000".to_owned()),
+            data: Some("0xa9059cbb".to_owned()),
+            gas: Some("21000".to_owned()),
+            gas_price: None,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            nonce: Some(7),
+        };
+        let typed = tx.validate()?;
+        assert_eq!(typed.value, Some(U256::from(10u64.pow(18))));
+        assert_eq!(
+            serde_json::to_value(&typed).ok(),
+            serde_json::to_value(&tx).ok().map(|mut v| {
+                v["to"] = "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913".into();
+                v
+            })
+        );
+        assert_eq!(EvmTransaction::from(typed.clone()).validate()?, typed);
+
+        let typo = EvmTransaction {
+            to: "0x833589fcd6edb6e08f4c7c32d4f71b54bda0291".to_owned(),
+            ..tx.clone()
+        };
+        assert!(matches!(typo.validate(), Err(PrimitiveError::InvalidAddress(_))));
+        let decimal_data = EvmTransaction { data: Some("12345".to_owned()), ..tx };
+        assert!(matches!(decimal_data.validate(), Err(PrimitiveError::InvalidHex(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn sign_request_wire_shape() -> Result<(), serde_json::Error> {
+        let personal = serde_json::to_value(SignRequest::personal("Hello, Bankr!"))?;
+        assert_eq!(
+            personal,
+            serde_json::json!({ "signatureType": "personal_sign", "message": "Hello, Bankr!" })
+        );
+        let bytes = serde_json::to_value(SignRequest::personal(vec![0xde, 0xad]))?;
+        assert_eq!(bytes["message"], "0xdead");
+
+        let tx =
+            EvmTransaction { value: Some("0".to_owned()), ..EvmTransaction::new("0xabc", 8453) };
+        let json = serde_json::to_value(SignRequest::transaction(tx))?;
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "signatureType": "eth_signTransaction",
+                "transaction": { "to": "0xabc", "chainId": 8453, "value": "0" }
+            })
+        );
+        let parsed: SignRequest = serde_json::from_value(json)?;
+        assert_eq!(parsed.signature_type(), SignatureType::EthSignTransaction);
+
+        let typed: SignRequest = serde_json::from_value(serde_json::json!({
+            "signatureType": "eth_signTypedData_v4",
+            "typedData": {
+                "domain": { "name": "MyApp", "version": "1", "chainId": 8453 },
+                "types": { "Mail": [{ "name": "contents", "type": "string" }] },
+                "primaryType": "Mail",
+                "message": { "contents": "hi" }
+            }
+        }))?;
+        assert!(
+            matches!(&typed.payload, SignPayload::TypedDataV4(d) if d.domain.chain_id == Some(8453))
+        );
+
+        let mismatched = serde_json::json!({ "signatureType": "personal_sign", "typedData": {} });
+        assert!(serde_json::from_value::<SignRequest>(mismatched).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn missing_target_is_an_error() -> Result<(), serde_json::Error> {
+        let tx: JobTransaction = serde_json::from_str(r#"{"type":"approval","metadata":{}}"#)?;
+        assert!(matches!(
+            EvmTransaction::try_from(&tx),
+            Err(crate::error::BankrError::Deserialization(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn domain_chain_id_accepts_strings() -> Result<(), serde_json::Error> {
+        for chain_id in [serde_json::json!(8453), "8453".into(), "0x2105".into(), "0X2105".into()] {
+            let domain: Eip712Domain = serde_json::from_value(serde_json::json!({
+                "name": "MyApp",
+                "chainId": chain_id,
+            }))?;
+            assert_eq!(domain.chain_id, Some(8453));
+            assert_eq!(serde_json::to_value(&domain)?["chainId"], 8453);
+        }
+        let domain: Eip712Domain = serde_json::from_str(r#"{"name":"MyApp"}"#)?;
+        assert_eq!(domain.chain_id, None);
+        assert!(serde_json::from_str::<Eip712Domain>(r#"{"chainId":"base"}"#).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,312 @@
+//! Human-readable token amounts.
+//!
+//! The API takes `value` and fee fields as integer base units (wei for native
+//! tokens). These helpers convert between that and amounts such as `"0.1 ETH"`,
+//! `"25 gwei"` or `"100 USDC"`.
+//!
+//! Native token symbols depend on the chain (`ETH` on Base, `POL` on
+//! Polygon), so they are only accepted by [`parse_native_amount`], which
+//! checks the symbol against the chain.
+//!
+//! ```rust
+//! use bankr_agent_api::{
+//!     chain::Chain,
+//!     units::{Unit, format_units, parse_amount, parse_native_amount, parse_token_amount},
+//! };
+//!
+//! assert_eq!(parse_native_amount("0.1 ETH", Chain::Base)?.to_string(), "100000000000000000");
+//! assert!(parse_native_amount("1 POL", Chain::Base).is_err());
+//! assert_eq!(parse_amount("25gwei")?.to_string(), "25000000000");
+//! assert_eq!(parse_amount("1000")?.to_string(), "1000");
+//! assert_eq!(parse_token_amount("100 USDC", "USDC", 6)?.to_string(), "100000000");
+//! assert_eq!(format_units(parse_amount("1.5 ether")?, Unit::Gwei.decimals()), "1500000000");
+//! # Ok::<(), bankr_agent_api::units::UnitsError>(())
+//! ```
+
+use std::{fmt, str::FromStr};
+
+use crate::{chain::Chain, primitives::U256};
+
+/// Error parsing a token amount.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum UnitsError {
+    /// Not a non-negative decimal number.
+    #[error("invalid amount `{0}`: expected a decimal number, optionally followed by a unit")]
+    InvalidAmount(String),
+    /// More fractional digits than the unit has decimals.
+    #[error("amount `{amount}` has more than {decimals} decimal places")]
+    TooPrecise {
+        /// The amount as given.
+        amount: String,
+        /// Decimals of the unit it was given in.
+        decimals: u8,
+    },
+    /// A fractional amount without a unit.
+    #[error("amount `{0}` needs a unit, e.g. `{0} ether` (bare integers are wei)")]
+    MissingUnit(String),
+    /// A unit or token symbol that is not recognised.
+    #[error("unknown unit `{0}`")]
+    UnknownUnit(String),
+    /// A native token symbol of a different chain, e.g. `POL` on Base.
+    #[error("`{symbol}` is not the native token of {chain}; use {native}, ether, gwei or wei")]
+    WrongNativeToken {
+        /// The symbol as given.
+        symbol: String,
+        /// Name of the chain the amount is for.
+        chain: &'static str,
+        /// That chain's native token symbol.
+        native: &'static str,
+    },
+    /// A native token symbol without a chain to check it against.
+    #[error("`{0}` depends on the chain; use ether, gwei or wei")]
+    NativeSymbolWithoutChain(String),
+    /// Larger than 2^256 - 1 base units.
+    #[error("amount `{0}` does not fit in 256 bits")]
+    Overflow(String),
+}
+
+/// A denomination of a chain's native token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Unit {
+    /// 10^0 wei.
+    Wei,
+    /// 10^9 wei.
+    Gwei,
+    /// 10^18 wei.
+    Ether,
+}
+
+impl Unit {
+    /// Number of decimal places relative to wei.
+    pub const fn decimals(self) -> u8 {
+        match self {
+            Self::Wei => 0,
+            Self::Gwei => 9,
+            Self::Ether => 18,
+        }
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Wei => "wei",
+            Self::Gwei => "gwei",
+            Self::Ether => "ether",
+        })
+    }
+}
+
+/// Case-insensitive: `wei`, `gwei` or `ether`. Token symbols such as `ETH`
+/// are chain-specific; see [`parse_native_amount`].
+impl FromStr for Unit {
+    type Err = UnitsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "wei" => Ok(Self::Wei),
+            "gwei" => Ok(Self::Gwei),
+            "ether" => Ok(Self::Ether),
+            _ if is_native_symbol(s) => Err(UnitsError::NativeSymbolWithoutChain(s.to_owned())),
+            _ => Err(UnitsError::UnknownUnit(s.to_owned())),
+        }
+    }
+}
+
+/// Parse a native-token amount into wei.
+///
+/// A bare integer is taken as wei, so existing raw values keep working;
+/// anything else needs a [`Unit`] suffix, with or without a space
+/// (`"0.1 ether"`, `"0.1ether"`, `"2gwei"`). Token symbols like `ETH` are
+/// rejected; use [`parse_native_amount`] to allow the chain's own.
+pub fn parse_amount(s: &str) -> Result<U256, UnitsError> {
+    let (number, unit) = split_unit(s)?;
+    let decimals = match unit {
+        "" if number.contains('.') => return Err(UnitsError::MissingUnit(s.to_owned())),
+        "" => 0,
+        unit => unit.parse::<Unit>()?.decimals(),
+    };
+    parse_units(number, decimals).map_err(|e| with_amount(e, s))
+}
+
+/// Like [`parse_amount`], but also accepts `chain`'s native token symbol
+/// (`"0.1 ETH"` on Base, `"1 POL"` on Polygon).
+///
+/// Another chain's native symbol is an error rather than being read as
+/// ether, so `"1 POL"` on Base cannot turn into 1 ETH.
+pub fn parse_native_amount(s: &str, chain: Chain) -> Result<U256, UnitsError> {
+    let (number, unit) = split_unit(s)?;
+    if unit.eq_ignore_ascii_case(chain.native_symbol()) {
+        return parse_units(number, chain.native_decimals()).map_err(|e| with_amount(e, s));
+    }
+    parse_amount(s).map_err(|e| match e {
+        UnitsError::NativeSymbolWithoutChain(symbol) => UnitsError::WrongNativeToken {
+            symbol,
+            chain: chain.name(),
+            native: chain.native_symbol(),
+        },
+        other => other,
+    })
+}
+
+/// Whether `s` is the native token symbol of any chain in the registry.
+fn is_native_symbol(s: &str) -> bool {
+    Chain::ALL.iter().any(|c| c.native_symbol().eq_ignore_ascii_case(s))
+}
+
+/// Parse an amount of a token with `decimals` decimals into base units.
+///
+/// The amount may be followed by `symbol` (case-insensitive), e.g.
+/// `"100 USDC"` or `"100"` for USDC with 6 decimals.
+pub fn parse_token_amount(s: &str, symbol: &str, decimals: u8) -> Result<U256, UnitsError> {
+    let (number, unit) = split_unit(s)?;
+    if !unit.is_empty() && !unit.eq_ignore_ascii_case(symbol) {
+        return Err(UnitsError::UnknownUnit(unit.to_owned()));
+    }
+    parse_units(number, decimals).map_err(|e| with_amount(e, s))
+}
+
+/// Parse a plain decimal number (`"1.25"`) scaled by `10^decimals`.
+pub fn parse_units(number: &str, decimals: u8) -> Result<U256, UnitsError> {
+    let invalid = || UnitsError::InvalidAmount(number.to_owned());
+    let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+    let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if whole.is_empty() && fraction.is_empty() || !is_digits(whole) || !is_digits(fraction) {
+        return Err(invalid());
+    }
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.len() > usize::from(decimals) {
+        return Err(UnitsError::TooPrecise { amount: number.to_owned(), decimals });
+    }
+    let digits = format!("{whole}{fraction:0<width$}", width = usize::from(decimals));
+    let digits = digits.trim_start_matches('0');
+    if digits.is_empty() {
+        return Ok(U256::ZERO);
+    }
+    digits.parse().map_err(|_| UnitsError::Overflow(number.to_owned()))
+}
+
+/// Format base units as a decimal number with `decimals` decimals, without
+/// trailing zeros: `format_units(1_500_000.into(), 6) == "1.5"`.
+pub fn format_units(value: U256, decimals: u8) -> String {
+    let digits = value.to_string();
+    let decimals = usize::from(decimals);
+    let digits = format!("{digits:0>width$}", width = decimals + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - decimals);
+    match fraction.trim_end_matches('0') {
+        "" => whole.to_owned(),
+        fraction => format!("{whole}.{fraction}"),
+    }
+}
+
+/// Format wei as ether, e.g. `"0.1"`.
+pub fn format_ether(wei: U256) -> String {
+    format_units(wei, Unit::Ether.decimals())
+}
+
+/// Format wei as gwei, e.g. `"25"`.
+pub fn format_gwei(wei: U256) -> String {
+    format_units(wei, Unit::Gwei.decimals())
+}
+
+/// Split `"0.1 ETH"` / `"0.1eth"` into the number and the (possibly empty)
+/// unit.
+fn split_unit(s: &str) -> Result<(&str, &str), UnitsError> {
+    let trimmed = s.trim();
+    let end = trimmed.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(trimmed.len());
+    if end == 0 {
+        return Err(UnitsError::InvalidAmount(s.to_owned()));
+    }
+    let (number, unit) = trimmed.split_at(end);
+    Ok((number, unit.trim_start()))
+}
+
+/// Report errors against the full input rather than just its number.
+fn with_amount(err: UnitsError, amount: &str) -> UnitsError {
+    match err {
+        UnitsError::InvalidAmount(_) => UnitsError::InvalidAmount(amount.to_owned()),
+        UnitsError::TooPrecise { decimals, .. } => {
+            UnitsError::TooPrecise { amount: amount.to_owned(), decimals }
+        }
+        UnitsError::Overflow(_) => UnitsError::Overflow(amount.to_owned()),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn amount(s: &str) -> Result<String, UnitsError> {
+        parse_amount(s).map(|v| v.to_string())
+    }
+
+    #[test]
+    fn parses_native_amounts() -> Result<(), UnitsError> {
+        assert_eq!(amount("0.1 Ether")?, "100000000000000000");
+        assert_eq!(amount("0.1ether")?, "100000000000000000");
+        assert_eq!(amount("2gwei")?, "2000000000");
+        assert_eq!(amount("1.5 Gwei")?, "1500000000");
+        assert_eq!(amount(".5 ether")?, "500000000000000000");
+        assert_eq!(amount("21000")?, "21000");
+        assert_eq!(amount("7 wei")?, "7");
+        assert_eq!(amount("0 ether")?, "0");
+        Ok(())
+    }
+
+    #[test]
+    fn native_symbols_must_match_the_chain() -> Result<(), UnitsError> {
+        let native = |s, chain| parse_native_amount(s, chain).map(|v| v.to_string());
+        assert_eq!(native("0.1 ETH", Chain::Base)?, "100000000000000000");
+        assert_eq!(native(".5 eth", Chain::Ethereum)?, "500000000000000000");
+        assert_eq!(native("1 POL", Chain::Polygon)?, "1000000000000000000");
+        assert_eq!(native("2 gwei", Chain::Polygon)?, "2000000000");
+        assert_eq!(native("21000", Chain::Base)?, "21000");
+
+        assert!(matches!(
+            native("1pol", Chain::Base),
+            Err(UnitsError::WrongNativeToken { chain: "Base", native: "ETH", .. })
+        ));
+        assert!(matches!(
+            native("1 ETH", Chain::Polygon),
+            Err(UnitsError::WrongNativeToken { .. })
+        ));
+        assert!(matches!(native("1 btc", Chain::Base), Err(UnitsError::UnknownUnit(_))));
+        assert!(matches!(parse_amount("1 ETH"), Err(UnitsError::NativeSymbolWithoutChain(_))));
+        assert!(matches!(parse_amount("1 POL"), Err(UnitsError::NativeSymbolWithoutChain(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_bad_amounts() {
+        assert!(matches!(parse_amount("0.1"), Err(UnitsError::MissingUnit(_))));
+        assert!(matches!(parse_amount("1 btc"), Err(UnitsError::UnknownUnit(_))));
+        assert!(matches!(parse_amount("1.5 wei"), Err(UnitsError::TooPrecise { decimals: 0, .. })));
+        assert!(matches!(parse_amount("1.2.3 ether"), Err(UnitsError::InvalidAmount(_))));
+        assert!(matches!(parse_amount("-1 ether"), Err(UnitsError::InvalidAmount(_))));
+        assert!(matches!(parse_amount(". ether"), Err(UnitsError::InvalidAmount(_))));
+        assert!(matches!(parse_amount(""), Err(UnitsError::InvalidAmount(_))));
+        let huge = format!("{} ether", "9".repeat(70));
+        assert!(matches!(parse_amount(&huge), Err(UnitsError::Overflow(_))));
+    }
+
+    #[test]
+    fn parses_token_amounts() -> Result<(), UnitsError> {
+        assert_eq!(parse_token_amount("100 USDC", "USDC", 6)?.to_string(), "100000000");
+        assert_eq!(parse_token_amount("0.25", "usdc", 6)?.to_string(), "250000");
+        assert!(parse_token_amount("1 DAI", "USDC", 6).is_err());
+        assert!(parse_token_amount("0.0000001", "USDC", 6).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn formats_units() -> Result<(), UnitsError> {
+        assert_eq!(format_ether(parse_amount("0.1 ether")?), "0.1");
+        assert_eq!(format_ether(U256::ZERO), "0");
+        assert_eq!(format_ether(U256::from(1u64)), "0.000000000000000001");
+        assert_eq!(format_gwei(parse_amount("25 gwei")?), "25");
+        assert_eq!(format_units(U256::from(1_500_000u64), 6), "1.5");
+        assert_eq!(format_units(U256::from(42u64), 0), "42");
+        Ok(())
+    }
+}
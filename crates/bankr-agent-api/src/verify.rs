@@ -0,0 +1,228 @@
+//! Offline signature verification and signer recovery.
+//!
+//! Recovers the address behind a secp256k1 signature for each signature type
+//! `/agent/sign` produces:
+//!
+//! - `personal_sign`: EIP-191 message hash ([`recover_personal_sign`])
+//! - `eth_signTypedData_v4`: EIP-712 digest ([`recover_typed_data`])
+//! - `eth_signTransaction`: the sender of a signed raw transaction ([`recover_transaction_sender`],
+//!   decoded by [`SignedTransaction`])
+//!
+//! [`SignResponse::verify`] picks the right one for a request and checks the
+//! result against the reported `signer`.
+//!
+//! ```rust,no_run
+//! use bankr_agent_api::{BankrAgentClient, types::SignRequest};
+//!
+//! # async fn example(client: &BankrAgentClient) -> Result<(), Box<dyn std::error::Error>> {
+//! let req = SignRequest::personal("Sign in to MyApp");
+//! let resp = client.sign(&req).await?;
+//! let signer = resp.verify(&req)?;
+//! println!("signed by {signer}");
+//! # Ok(())
+//! # }
+//! ```
+
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+
+use crate::{
+    eip712::Eip712Error,
+    primitives::{Address, Bytes, keccak256},
+    transaction::SignedTransaction,
+    types::{SignPayload, SignRequest, SignResponse, TypedData},
+};
+
+/// Why a signature could not be verified.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum VerifyError {
+    /// The signature is malformed or does not recover to a public key.
+    #[error("invalid signature: {0}")]
+    InvalidSignature(String),
+    /// The signed transaction could not be decoded.
+    #[error("invalid signed transaction: {0}")]
+    InvalidTransaction(String),
+    /// The typed data could not be hashed.
+    #[error("invalid typed data: {0}")]
+    TypedData(#[from] Eip712Error),
+    /// A signed transaction differs from the one requested.
+    #[error("signed transaction {field} is {signed}, requested {requested}")]
+    FieldMismatch {
+        /// The transaction field that differs.
+        field: &'static str,
+        /// The requested value.
+        requested: String,
+        /// The value in the signed transaction.
+        signed: String,
+    },
+    /// The response has no signature to verify.
+    #[error("response has no signature")]
+    MissingSignature,
+    /// The signature recovers to a different address than expected.
+    #[error("signature recovers to {recovered}, expected {expected}")]
+    Mismatch {
+        /// The address the signature should belong to.
+        expected: Address,
+        /// The address it actually recovers to.
+        recovered: Address,
+    },
+}
+
+/// The EIP-191 hash signed by `personal_sign`:
+/// `keccak256("\x19Ethereum Signed Message:\n" ‖ len ‖ message)`.
+pub fn personal_message_hash(message: &[u8]) -> [u8; 32] {
+    let mut buf = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    buf.extend_from_slice(message);
+    keccak256(&buf)
+}
+
+/// Recover the signer of `digest` from a 65-byte `r ‖ s ‖ v` signature.
+///
+/// `v` may be `0`/`1` or `27`/`28`.
+pub fn recover_address(digest: &[u8; 32], signature: &[u8]) -> Result<Address, VerifyError> {
+    let [rs @ .., v] = signature else {
+        return Err(VerifyError::InvalidSignature("empty signature".to_owned()));
+    };
+    if rs.len() != 64 {
+        return Err(VerifyError::InvalidSignature(format!(
+            "expected 65 bytes, got {}",
+            signature.len()
+        )));
+    }
+    let parity = match v {
+        0 | 27 => false,
+        1 | 28 => true,
+        _ => return Err(VerifyError::InvalidSignature(format!("invalid recovery id {v}"))),
+    };
+    recover(digest, rs, parity)
+}
+
+/// Recover the signer of a `personal_sign` signature over `message`.
+pub fn recover_personal_sign(message: &[u8], signature: &[u8]) -> Result<Address, VerifyError> {
+    recover_address(&personal_message_hash(message), signature)
+}
+
+/// Recover the signer of an `eth_signTypedData_v4` signature over `data`.
+pub fn recover_typed_data(data: &TypedData, signature: &[u8]) -> Result<Address, VerifyError> {
+    recover_address(&data.digest()?, signature)
+}
+
+/// Recover the sender of a signed raw transaction (legacy, EIP-155,
+/// EIP-2930 or EIP-1559).
+pub fn recover_transaction_sender(raw: &[u8]) -> Result<Address, VerifyError> {
+    SignedTransaction::decode(raw)?.recover_sender()
+}
+
+impl SignResponse {
+    /// Recover the signer of this response's signature over `req`, and check
+    /// it against [`signer`](Self::signer) when one is reported.
+    ///
+    /// For `eth_signTransaction` the signature is the signed raw transaction:
+    /// it must [`check`](SignedTransaction::check) against the requested
+    /// transaction, and the recovered address is its sender.
+    pub fn verify(&self, req: &SignRequest) -> Result<Address, VerifyError> {
+        let signature: Bytes = self
+            .signature
+            .as_deref()
+            .ok_or(VerifyError::MissingSignature)?
+            .parse()
+            .map_err(|e| VerifyError::InvalidSignature(format!("{e}")))?;
+
+        let recovered = match &req.payload {
+            SignPayload::PersonalSign(message) => {
+                recover_personal_sign(message.as_bytes(), signature.as_slice())?
+            }
+            SignPayload::TypedDataV4(data) => recover_typed_data(data, signature.as_slice())?,
+            SignPayload::Transaction(requested) => {
+                let tx = SignedTransaction::decode(signature.as_slice())?;
+                tx.check(requested)?;
+                tx.recover_sender()?
+            }
+        };
+
+        if let Some(signer) = &self.signer {
+            let expected: Address = signer
+                .parse()
+                .map_err(|e| VerifyError::InvalidSignature(format!("reported signer: {e}")))?;
+            if expected != recovered {
+                return Err(VerifyError::Mismatch { expected, recovered });
+            }
+        }
+        Ok(recovered)
+    }
+}
+
+pub(crate) fn recover(digest: &[u8; 32], rs: &[u8], parity: bool) -> Result<Address, VerifyError> {
+    let invalid = |e: k256::ecdsa::Error| VerifyError::InvalidSignature(e.to_string());
+    let signature = Signature::from_slice(rs).map_err(invalid)?;
+    let key =
+        VerifyingKey::recover_from_prehash(digest, &signature, RecoveryId::new(parity, false))
+            .map_err(invalid)?;
+    Ok(address_of(&key))
+}
+
+/// The address of a public key: the last 20 bytes of the Keccak-256 hash of
+/// its uncompressed coordinates.
+fn address_of(key: &VerifyingKey) -> Address {
+    let point = key.to_encoded_point(false);
+    let hash = keccak256(&point.as_bytes()[1..]);
+    let mut bytes = [0; 20];
+    bytes.copy_from_slice(&hash[12..]);
+    Address::from_bytes(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use k256::ecdsa::SigningKey;
+
+    use super::*;
+    use crate::primitives::decode_hex;
+
+    fn hex(s: &str) -> Vec<u8> {
+        decode_hex(s.trim_start_matches("0x")).unwrap_or_default()
+    }
+
+    fn sign(key: &SigningKey, digest: &[u8; 32]) -> Result<Vec<u8>, k256::ecdsa::Error> {
+        let (signature, recovery_id) = key.sign_prehash_recoverable(digest)?;
+        let mut out = signature.to_bytes().to_vec();
+        out.push(27 + recovery_id.to_byte());
+        Ok(out)
+    }
+
+    #[test]
+    fn personal_sign_known_vector() -> Result<(), VerifyError> {
+        // web3.js `accounts.sign("Some data", 0x4c08…2318)`.
+        let signature = hex("0xb91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd\
+             6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c");
+        assert_eq!(
+            personal_message_hash(b"Some data").to_vec(),
+            hex("0x1da44b586eb0729ff70a73c326926f6ed5a25f5b056e7f47fbc6e58d86871655")
+        );
+        let signer = recover_personal_sign(b"Some data", &signature)?;
+        assert_eq!(signer.to_string(), "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23");
+        Ok(())
+    }
+
+    #[test]
+    fn sign_response_verify() -> Result<(), Box<dyn std::error::Error>> {
+        let key = SigningKey::from_slice(&[0x11; 32])?;
+        let signer = address_of(key.verifying_key());
+        let req = SignRequest::personal("hello");
+        let mut resp = SignResponse {
+            success: true,
+            signature: Some(Bytes::from(sign(&key, &personal_message_hash(b"hello"))?).to_string()),
+            signer: Some(signer.to_string()),
+            signature_type: None,
+            error: None,
+        };
+        assert_eq!(resp.verify(&req)?, signer);
+
+        resp.signer = Some(Address::ZERO.to_string());
+        assert!(
+            matches!(resp.verify(&req), Err(VerifyError::Mismatch { recovered, .. }) if recovered == signer)
+        );
+
+        resp.signature = None;
+        assert_eq!(resp.verify(&req), Err(VerifyError::MissingSignature));
+        Ok(())
+    }
+}
@@ -0,0 +1,224 @@
+//! Streaming job progress.
+//!
+//! [`watch_job`] polls a job and turns each snapshot into deduplicated
+//! [`JobEvent`]s: status transitions, new status-update messages, and the
+//! final result.
+//!
+//! ```rust,no_run
+//! use bankr_agent_api::{BankrAgentClient, watch::JobEvent};
+//! use futures_util::StreamExt;
+//!
+//! # async fn example(client: &BankrAgentClient) -> Result<(), bankr_agent_api::error::BankrError> {
+//! let mut events = std::pin::pin!(client.watch_job("job_abc123"));
+//! while let Some(event) = events.next().await {
+//!     match event? {
+//!         JobEvent::StatusChanged(status) => println!("status: {status}"),
+//!         JobEvent::Update(update) => println!("{}", update.message.unwrap_or_default()),
+//!         JobEvent::Finished(job) => println!("done: {:?}", job.response),
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{collections::VecDeque, time::Duration};
+
+use futures_util::{Stream, stream};
+use tracing::debug;
+
+use crate::{
+    api::BankrApi,
+    error::BankrError,
+    poll::{PollConfig, PollSchedule},
+    types::{JobResponse, JobStatus, StatusUpdate},
+};
+
+/// A progress event emitted by [`watch_job`].
+#[derive(Debug, Clone)]
+pub enum JobEvent {
+    /// The job moved to a new non-terminal status (`pending` or
+    /// `processing`). The first snapshot always produces one.
+    StatusChanged(JobStatus),
+    /// A status-update message not seen in a previous snapshot.
+    Update(StatusUpdate),
+    /// The job reached `completed`, `failed` or `cancelled`. This is always
+    /// the last event.
+    Finished(Box<JobResponse>),
+}
+
+struct Watch<'a, A: ?Sized> {
+    api: &'a A,
+    job_id: String,
+    schedule: PollSchedule,
+    delay: Option<Duration>,
+    status: Option<JobStatus>,
+    seen_updates: usize,
+    queue: VecDeque<Result<JobEvent, BankrError>>,
+    done: bool,
+}
+
+impl<A: BankrApi + ?Sized> Watch<'_, A> {
+    /// Fetch the next snapshot and queue the events it produces.
+    async fn poll(&mut self) {
+        if let Some(delay) = self.delay {
+            tokio::time::sleep(delay).await;
+        }
+
+        let job = match self.api.get_job(&self.job_id).await {
+            Ok(job) => job,
+            Err(e) => {
+                self.finish(Err(e));
+                return;
+            }
+        };
+        debug!(attempt = self.schedule.attempts() + 1, status = %job.status, "Watch poll");
+
+        let terminal = job.status.is_terminal();
+        if !terminal && self.status.as_ref() != Some(&job.status) {
+            self.queue.push_back(Ok(JobEvent::StatusChanged(job.status.clone())));
+        }
+        self.status = Some(job.status.clone());
+
+        let updates = job.status_updates.as_deref().unwrap_or_default();
+        for update in updates.iter().skip(self.seen_updates) {
+            self.queue.push_back(Ok(JobEvent::Update(update.clone())));
+        }
+        self.seen_updates = self.seen_updates.max(updates.len());
+
+        if terminal {
+            self.finish(Ok(JobEvent::Finished(Box::new(job))));
+            return;
+        }
+        self.delay = self.schedule.after_attempt();
+        if self.delay.is_none() {
+            self.finish(Err(BankrError::PollTimeout {
+                attempts: self.schedule.attempts(),
+                last_job: Some(Box::new(job)),
+            }));
+        }
+    }
+
+    fn finish(&mut self, last: Result<JobEvent, BankrError>) {
+        self.queue.push_back(last);
+        self.done = true;
+    }
+}
+
+/// Watch `job_id` via [`BankrApi::get_job`], polling as configured by
+/// `config`.
+///
+/// The stream ends after [`JobEvent::Finished`], or after yielding a single
+/// error: the first failed request, or [`BankrError::PollTimeout`] once the
+/// poll budget is exhausted without reaching a terminal state. A failed or
+/// cancelled job is reported as `Finished`, not as an error.
+pub fn watch_job<'a, A: BankrApi + ?Sized>(
+    api: &'a A,
+    job_id: &str,
+    config: &PollConfig,
+) -> impl Stream<Item = Result<JobEvent, BankrError>> + Send + 'a {
+    let state = Watch {
+        api,
+        job_id: job_id.to_owned(),
+        schedule: PollSchedule::new(config),
+        delay: None,
+        status: None,
+        seen_updates: 0,
+        queue: VecDeque::new(),
+        done: false,
+    };
+
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(event) = state.queue.pop_front() {
+                return Some((event, state));
+            }
+            if state.done {
+                return None;
+            }
+            state.poll().await;
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::StreamExt;
+
+    use super::*;
+    use crate::mock::MockBankrApi;
+
+    fn update(message: &str) -> StatusUpdate {
+        StatusUpdate { message: Some(message.to_owned()), timestamp: None }
+    }
+
+    fn job(status: JobStatus, updates: &[&str]) -> JobResponse {
+        JobResponse {
+            status_updates: Some(updates.iter().map(|m| update(m)).collect()),
+            ..JobResponse::new("job_1", status)
+        }
+    }
+
+    fn describe(event: &Result<JobEvent, BankrError>) -> String {
+        match event {
+            Ok(JobEvent::StatusChanged(status)) => format!("status:{status}"),
+            Ok(JobEvent::Update(update)) => {
+                format!("update:{}", update.message.as_deref().unwrap_or(""))
+            }
+            Ok(JobEvent::Finished(job)) => format!("finished:{}", job.status),
+            Err(e) => format!("error:{e}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn emits_deduplicated_events() {
+        let mock = MockBankrApi::new();
+        mock.push_get_job(Ok(job(JobStatus::Pending, &[])));
+        mock.push_get_job(Ok(job(JobStatus::Pending, &[])));
+        mock.push_get_job(Ok(job(JobStatus::Processing, &["Checking balance"])));
+        mock.push_get_job(Ok(job(JobStatus::Processing, &["Checking balance", "Swapping"])));
+        mock.push_get_job(Ok(job(JobStatus::Completed, &["Checking balance", "Swapping", "Done"])));
+
+        let events: Vec<_> =
+            watch_job(&mock, "job_1", &PollConfig::fixed(Duration::ZERO, 10)).collect().await;
+        let events: Vec<_> = events.iter().map(describe).collect();
+        assert_eq!(
+            events,
+            [
+                "status:pending",
+                "status:processing",
+                "update:Checking balance",
+                "update:Swapping",
+                "update:Done",
+                "finished:completed",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_job_is_finished_event() {
+        let mock = MockBankrApi::new();
+        mock.push_get_job(Ok(job(JobStatus::Failed, &[])));
+
+        let events: Vec<_> =
+            watch_job(&mock, "job_1", &PollConfig::fixed(Duration::ZERO, 10)).collect().await;
+        assert_eq!(events.iter().map(describe).collect::<Vec<_>>(), ["finished:failed"]);
+    }
+
+    #[tokio::test]
+    async fn ends_after_error_or_timeout() {
+        let mock = MockBankrApi::new();
+        mock.push_get_job(Ok(job(JobStatus::Pending, &[])));
+        mock.push_get_job(Err(BankrError::NotFound { body: Default::default() }));
+
+        let events: Vec<_> =
+            watch_job(&mock, "job_1", &PollConfig::fixed(Duration::ZERO, 10)).collect().await;
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[1], Err(BankrError::NotFound { .. })));
+
+        mock.push_get_job(Ok(job(JobStatus::Processing, &[])));
+        let events: Vec<_> =
+            watch_job(&mock, "job_1", &PollConfig::fixed(Duration::ZERO, 1)).collect().await;
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[1], Err(BankrError::PollTimeout { attempts: 1, .. })));
+    }
+}